@@ -0,0 +1,49 @@
+use std::{fs, ops::Deref};
+
+/// Input files at least this big get memory-mapped instead of being copied onto the heap
+#[cfg(feature = "mmap")]
+const MMAP_THRESHOLD_BYTES: u64 = 64 * 1024 * 1024;
+
+/// The entire puzzle input held in a single buffer.
+/// Parsers borrow `&str` slices out of this, so no per-line Strings are allocated.
+pub enum InputBuffer {
+    Owned(String),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for InputBuffer {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Self::Owned(input_string) => input_string,
+            // SAFETY: read_input() checks the mapped bytes are valid UTF-8 before handing them out
+            #[cfg(feature = "mmap")]
+            Self::Mapped(mapped_file) => unsafe { std::str::from_utf8_unchecked(mapped_file) },
+        }
+    }
+}
+
+/// Read the input file into a single buffer.
+/// With the `mmap` feature enabled, very large files are memory-mapped rather than read.
+pub fn read_input(input_file_name: &str) -> InputBuffer {
+    #[cfg(feature = "mmap")]
+    {
+        let input_file = fs::File::open(input_file_name).expect("Could not open input file!");
+        let input_file_size = input_file
+            .metadata()
+            .expect("Could not read input file metadata!")
+            .len();
+
+        if input_file_size >= MMAP_THRESHOLD_BYTES {
+            // SAFETY: The input file is not expected to be modified while we are solving the puzzle
+            let mapped_file = unsafe { memmap2::Mmap::map(&input_file) }
+                .expect("Could not memory-map input file!");
+            std::str::from_utf8(&mapped_file).expect("Input file is not valid UTF-8!");
+            return InputBuffer::Mapped(mapped_file);
+        }
+    }
+
+    InputBuffer::Owned(fs::read_to_string(input_file_name).expect("Could not read input file!"))
+}
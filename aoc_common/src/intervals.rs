@@ -0,0 +1,88 @@
+/// Merge inclusive (start, end) ranges into the fewest ranges covering the same positions.
+/// Ranges that overlap, or sit right next to each other, become one.
+/// The result is sorted, and no two of its ranges touch
+pub fn merge_intervals(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    intervals.sort_unstable();
+
+    let mut merged_intervals: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+
+    for (interval_start, interval_end) in intervals {
+        match merged_intervals.last_mut() {
+            // Sorted by start, so this interval can only extend the last merged one
+            Some((_, merged_end)) if interval_start <= *merged_end + 1 => {
+                *merged_end = (*merged_end).max(interval_end);
+            }
+            _ => merged_intervals.push((interval_start, interval_end)),
+        }
+    }
+
+    merged_intervals
+}
+
+/// Number of positions covered by the inclusive ranges. The ranges must not overlap
+pub fn get_covered_size(intervals: &[(i64, i64)]) -> usize {
+    intervals
+        .iter()
+        .map(|(interval_start, interval_end)| (interval_end - interval_start + 1) as usize)
+        .sum()
+}
+
+/// Number of positions covered by both inclusive ranges.
+/// Whatever the merged ranges don't cover twice is the overlap
+pub fn get_overlap_size(interval_a: (i64, i64), interval_b: (i64, i64)) -> usize {
+    get_covered_size(&[interval_a]) + get_covered_size(&[interval_b])
+        - get_covered_size(&merge_intervals(vec![interval_a, interval_b]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn merge_intervals_joins_overlapping_and_adjacent() {
+        assert_eq!(
+            merge_intervals(vec![(12, 12), (2, 14), (-2, 2), (16, 24), (15, 15)]),
+            vec![(-2, 24)]
+        );
+        assert_eq!(merge_intervals(vec![(5, 6), (0, 3)]), vec![(0, 3), (5, 6)]);
+        assert_eq!(merge_intervals(vec![]), vec![]);
+    }
+
+    #[test]
+    fn overlap_size_of_two_intervals() {
+        assert_eq!(get_overlap_size((2, 8), (3, 7)), 5);
+        assert_eq!(get_overlap_size((5, 7), (7, 9)), 1);
+        // Next to each other, so merged into one, but not overlapping
+        assert_eq!(get_overlap_size((2, 3), (4, 5)), 0);
+        assert_eq!(get_overlap_size((2, 4), (6, 8)), 0);
+    }
+
+    fn any_intervals() -> impl Strategy<Value = Vec<(i64, i64)>> {
+        prop::collection::vec(
+            (-50i64..50, 0i64..20).prop_map(|(start, length)| (start, start + length)),
+            0..10,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn merged_intervals_are_sorted_and_apart(intervals in any_intervals()) {
+            let merged_intervals = merge_intervals(intervals);
+
+            for merged_pair in merged_intervals.windows(2) {
+                prop_assert!(merged_pair[0].1 + 1 < merged_pair[1].0);
+            }
+        }
+
+        #[test]
+        fn merged_intervals_cover_the_same_positions(intervals in any_intervals(), position in -60i64..80) {
+            let is_in_any = |interval_list: &[(i64, i64)]| {
+                interval_list.iter().any(|&(start, end)| (start..=end).contains(&position))
+            };
+
+            let merged_intervals = merge_intervals(intervals.clone());
+            prop_assert_eq!(is_in_any(&intervals), is_in_any(&merged_intervals));
+        }
+    }
+}
@@ -0,0 +1,4 @@
+//! Code shared by several days, pulled in as a path dependency so each day stays its own crate
+
+pub mod input;
+pub mod intervals;
@@ -0,0 +1,892 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    io::{self, BufRead, Read, Write},
+    str::FromStr,
+};
+
+use log::{debug, trace};
+
+use crate::inventory::{
+    read_elves, ElfInventory, InventoryError, InventoryIssue, InventorySummary, ValidationMode,
+};
+
+/// The ways an inventory can be written down:
+///
+/// Plain, the puzzle's own format: one calorie count per line, with a blank line after each elf
+///
+/// Csv: "elf_id,calories" rows, one per snack, after an optional "elf_id,calories" header.
+/// An elf's rows don't have to be next to each other, and a row with no calories is an elf carrying nothing
+///
+/// Json: an array holding one array of calorie counts per elf, like "[[1000, 2000, 3000], [4000]]"
+///
+/// Named: an "Elf Alice:" header line before each elf's calorie counts. Blank lines between elves are optional
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InventoryFormat {
+    Plain,
+    Csv,
+    Json,
+    Named,
+}
+
+impl FromStr for InventoryFormat {
+    type Err = String;
+
+    fn from_str(format_str: &str) -> Result<Self, Self::Err> {
+        match format_str.to_ascii_lowercase().as_str() {
+            "plain" => Ok(InventoryFormat::Plain),
+            "csv" => Ok(InventoryFormat::Csv),
+            "json" => Ok(InventoryFormat::Json),
+            "named" => Ok(InventoryFormat::Named),
+            strange_value => Err(format!(
+                "unknown inventory format \"{}\". Expected plain, csv, json or named",
+                strange_value
+            )),
+        }
+    }
+}
+
+impl fmt::Display for InventoryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format_str = match self {
+            Self::Plain => "plain",
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Named => "named",
+        };
+        write!(f, "{}", format_str)
+    }
+}
+
+const CSV_HEADER: &str = "elf_id,calories";
+
+/// A reader that gives back the start of the input that detect_format read, and then the rest of it
+pub type DetectedReader<R> = io::Chain<io::Cursor<Vec<u8>>, R>;
+
+/// Work out the format from the start of the input. The first line that isn't blank decides it:
+/// a '[' is JSON, an "Elf ...:" line is named blocks, and a line with a comma is CSV. Anything else is plain.
+///
+/// Whole lines are read until one isn't blank, however the input arrives, as a pipe can hand over
+/// a line a few bytes at a time. Returns a reader over the whole input, including what was read here
+pub fn detect_format<R: BufRead>(
+    mut reader: R,
+) -> io::Result<(InventoryFormat, DetectedReader<R>)> {
+    let mut input_start: Vec<u8> = Vec::new();
+    let first_line = loop {
+        let line_start = input_start.len();
+        if reader.read_until(b'\n', &mut input_start)? == 0 {
+            // Nothing but blank lines, if anything
+            break String::new();
+        }
+
+        let input_line = String::from_utf8_lossy(&input_start[line_start..]);
+        if !input_line.trim().is_empty() {
+            break input_line.trim().to_string();
+        }
+    };
+
+    let input_format = if first_line.starts_with('[') {
+        InventoryFormat::Json
+    } else if get_named_elf_header(&first_line).is_some() {
+        InventoryFormat::Named
+    } else if first_line.contains(',') {
+        InventoryFormat::Csv
+    } else {
+        InventoryFormat::Plain
+    };
+    debug!("Detected {} inventory format", input_format);
+
+    Ok((input_format, io::Cursor::new(input_start).chain(reader)))
+}
+
+/// The given format, or the detected one with None. Either way, with a reader over the whole input
+pub fn resolve_format<R: BufRead>(
+    reader: R,
+    input_format: Option<InventoryFormat>,
+) -> io::Result<(InventoryFormat, DetectedReader<R>)> {
+    match input_format {
+        Some(input_format) => Ok((input_format, io::Cursor::new(Vec::new()).chain(reader))),
+        None => detect_format(reader),
+    }
+}
+
+/// Read a whole inventory in the given format, or in whichever format it looks like with None.
+/// Every format gets the same checks as a plain inventory, and fails or counts issues the same way
+pub fn read_inventory<R: BufRead>(
+    reader: R,
+    input_format: Option<InventoryFormat>,
+    validation_mode: ValidationMode,
+) -> Result<(Vec<ElfInventory>, InventorySummary), InventoryError> {
+    let (input_format, mut reader) = resolve_format(reader, input_format)?;
+    if input_format == InventoryFormat::Plain {
+        return read_elves(reader, validation_mode);
+    }
+
+    let mut input_string = String::new();
+    reader.read_to_string(&mut input_string)?;
+
+    let mut inventory_builder = InventoryBuilder::new(validation_mode);
+    match input_format {
+        InventoryFormat::Plain => unreachable!("Plain inventories are read by read_elves"),
+        InventoryFormat::Csv => parse_csv(&input_string, &mut inventory_builder)?,
+        InventoryFormat::Json => parse_json(&input_string, &mut inventory_builder)?,
+        InventoryFormat::Named => parse_named(&input_string, &mut inventory_builder)?,
+    }
+
+    inventory_builder.finish(input_string.lines().count())
+}
+
+/// Write the inventory in the given format. Reading it back in that format gives the same elves and snacks.
+/// Names are kept by the CSV and named formats, and elves without one are written under their elf number
+pub fn write_inventory<W: Write>(
+    mut writer: W,
+    elf_inventories: &[ElfInventory],
+    output_format: InventoryFormat,
+) -> io::Result<()> {
+    let get_elf_id = |elf_inventory: &ElfInventory| {
+        elf_inventory
+            .elf_name
+            .clone()
+            .unwrap_or_else(|| elf_inventory.elf_number.to_string())
+    };
+
+    match output_format {
+        InventoryFormat::Plain => {
+            // Every elf ends with a blank line, so an elf carrying nothing is just a blank line
+            for elf_inventory in elf_inventories {
+                for calories in &elf_inventory.snack_calories {
+                    writeln!(writer, "{}", calories)?;
+                }
+                writeln!(writer)?;
+            }
+        }
+        InventoryFormat::Csv => {
+            writeln!(writer, "{}", CSV_HEADER)?;
+            for elf_inventory in elf_inventories {
+                let elf_id = get_elf_id(elf_inventory);
+                if elf_inventory.snack_calories.is_empty() {
+                    writeln!(writer, "{},", elf_id)?;
+                }
+                for calories in &elf_inventory.snack_calories {
+                    writeln!(writer, "{},{}", elf_id, calories)?;
+                }
+            }
+        }
+        InventoryFormat::Json => {
+            writeln!(writer, "[")?;
+            for (elf_index, elf_inventory) in elf_inventories.iter().enumerate() {
+                let calorie_strs: Vec<String> = elf_inventory
+                    .snack_calories
+                    .iter()
+                    .map(|calories| calories.to_string())
+                    .collect();
+                let separator = if elf_index + 1 < elf_inventories.len() {
+                    ","
+                } else {
+                    ""
+                };
+                writeln!(writer, "  [{}]{}", calorie_strs.join(", "), separator)?;
+            }
+            writeln!(writer, "]")?;
+        }
+        InventoryFormat::Named => {
+            for (elf_index, elf_inventory) in elf_inventories.iter().enumerate() {
+                if elf_index > 0 {
+                    writeln!(writer)?;
+                }
+                writeln!(writer, "Elf {}:", get_elf_id(elf_inventory))?;
+                for calories in &elf_inventory.snack_calories {
+                    writeln!(writer, "{}", calories)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The name in an "Elf Alice:" header line
+fn get_named_elf_header(input_line: &str) -> Option<&str> {
+    input_line
+        .strip_prefix("Elf ")
+        .and_then(|header_rest| header_rest.strip_suffix(':'))
+        .map(|elf_id| elf_id.trim())
+}
+
+/// Collects the elves for any format other than plain, with the same checks for_each_elf makes on plain ones.
+///
+/// An elf ID that's just a number, like the 7 in "7,100" or "Elf 7:", is the elf's number. Named elves, and every
+/// elf in a JSON inventory, are numbered once all the elves are in: each takes the lowest number no numbered elf has,
+/// in the order they appear. So an inventory with no numbers in it is numbered 1, 2, 3... like a plain one
+struct InventoryBuilder {
+    validation_mode: ValidationMode,
+    elf_inventories: Vec<ElfInventory>,
+    /// The number each elf was given in the input, if any
+    elf_ids: Vec<Option<usize>>,
+    elf_totals: Vec<i64>,
+    /// The line each elf started on, to report the elves that never get any snacks
+    elf_start_lines: Vec<usize>,
+    inventory_issues: Vec<InventoryIssue>,
+    inventory_summary: InventorySummary,
+}
+
+impl InventoryBuilder {
+    fn new(validation_mode: ValidationMode) -> InventoryBuilder {
+        InventoryBuilder {
+            validation_mode,
+            elf_inventories: Vec::new(),
+            elf_ids: Vec::new(),
+            elf_totals: Vec::new(),
+            elf_start_lines: Vec::new(),
+            inventory_issues: Vec::new(),
+            inventory_summary: InventorySummary::default(),
+        }
+    }
+
+    /// Returns the new elf's index. elf_id is the number or name the input gives the elf, if it gives one
+    fn add_elf(&mut self, elf_id: Option<&str>, line_number: usize) -> usize {
+        let elf_number = elf_id.and_then(|elf_id| elf_id.parse::<usize>().ok());
+        let elf_name = match (elf_id, elf_number) {
+            (Some(elf_id), None) => Some(elf_id.to_string()),
+            _ => None,
+        };
+
+        self.elf_inventories.push(ElfInventory {
+            // Set by finish
+            elf_number: 0,
+            elf_name,
+            snack_calories: Vec::new(),
+        });
+        self.elf_ids.push(elf_number);
+        self.elf_totals.push(0);
+        self.elf_start_lines.push(line_number);
+
+        self.elf_inventories.len() - 1
+    }
+
+    fn add_snack(
+        &mut self,
+        elf_index: usize,
+        calories: i64,
+        line_number: usize,
+    ) -> Result<(), InventoryError> {
+        if calories < 0 {
+            self.report_issue(InventoryIssue::NegativeCalories {
+                line_number,
+                calories,
+            });
+        }
+
+        // A named elf doesn't have a number yet, so it's reported by its place in the input
+        self.elf_totals[elf_index] = self.elf_totals[elf_index].checked_add(calories).ok_or(
+            InventoryError::ElfOverflow {
+                elf_number: self.elf_ids[elf_index].unwrap_or(elf_index + 1),
+                line_number,
+            },
+        )?;
+        self.elf_inventories[elf_index]
+            .snack_calories
+            .push(calories);
+
+        Ok(())
+    }
+
+    fn report_issue(&mut self, inventory_issue: InventoryIssue) {
+        trace!("Inventory issue: {}", inventory_issue);
+        match self.validation_mode {
+            ValidationMode::Strict => self.inventory_issues.push(inventory_issue),
+            ValidationMode::Lenient => self.inventory_summary.ignored.count(&inventory_issue),
+        }
+    }
+
+    fn finish(
+        mut self,
+        line_count: usize,
+    ) -> Result<(Vec<ElfInventory>, InventorySummary), InventoryError> {
+        // An ID already used by an earlier elf is an issue. A repeated number is dropped,
+        // and that elf is numbered along with the named ones
+        let mut used_elf_numbers: HashSet<usize> = HashSet::new();
+        let mut used_elf_names: HashSet<String> = HashSet::new();
+        for elf_index in 0..self.elf_inventories.len() {
+            let repeated_elf_id = match (
+                self.elf_ids[elf_index],
+                &self.elf_inventories[elf_index].elf_name,
+            ) {
+                (Some(elf_number), _) if !used_elf_numbers.insert(elf_number) => {
+                    self.elf_ids[elf_index] = None;
+                    Some(elf_number.to_string())
+                }
+                (None, Some(elf_name)) if !used_elf_names.insert(elf_name.clone()) => {
+                    Some(elf_name.clone())
+                }
+                _ => None,
+            };
+
+            if let Some(elf_id) = repeated_elf_id {
+                self.report_issue(InventoryIssue::RepeatedElfId {
+                    line_number: self.elf_start_lines[elf_index],
+                    elf_id,
+                });
+            }
+        }
+
+        let mut next_free_number = 1;
+        for elf_index in 0..self.elf_inventories.len() {
+            let elf_number = match self.elf_ids[elf_index] {
+                Some(elf_number) => elf_number,
+                None => {
+                    while used_elf_numbers.contains(&next_free_number) {
+                        next_free_number += 1;
+                    }
+                    used_elf_numbers.insert(next_free_number);
+                    next_free_number
+                }
+            };
+            self.elf_inventories[elf_index].elf_number = elf_number;
+
+            if self.elf_inventories[elf_index].snack_calories.is_empty() {
+                self.report_issue(InventoryIssue::EmptyElf {
+                    line_number: self.elf_start_lines[elf_index],
+                    elf_number,
+                });
+            }
+        }
+
+        if !self.inventory_issues.is_empty() {
+            // Empty elves and repeated IDs are only found at the end, so put them back in line order
+            self.inventory_issues
+                .sort_by_key(|inventory_issue| match inventory_issue {
+                    InventoryIssue::MalformedLine { line_number, .. }
+                    | InventoryIssue::NegativeCalories { line_number, .. }
+                    | InventoryIssue::EmptyElf { line_number, .. }
+                    | InventoryIssue::RepeatedElfId { line_number, .. } => *line_number,
+                });
+            return Err(InventoryError::Invalid(self.inventory_issues));
+        }
+
+        self.inventory_summary.elf_count = self.elf_inventories.len();
+        self.inventory_summary.line_count = line_count;
+        Ok((self.elf_inventories, self.inventory_summary))
+    }
+}
+
+fn parse_csv(
+    input_string: &str,
+    inventory_builder: &mut InventoryBuilder,
+) -> Result<(), InventoryError> {
+    // Elf ID -> Elf index. Rows for the same elf can be anywhere in the file
+    let mut elf_indices: HashMap<String, usize> = HashMap::new();
+
+    for (line_index, input_line) in input_string.lines().enumerate() {
+        let line_number = line_index + 1;
+        let input_line = input_line.trim();
+        if input_line.is_empty() || input_line.replace(' ', "") == CSV_HEADER {
+            continue;
+        }
+
+        // Split on the last comma, so names with commas in them still work
+        let Some((elf_id, calories_str)) = input_line
+            .rsplit_once(',')
+            .map(|(elf_id, calories_str)| (elf_id.trim(), calories_str.trim()))
+            .filter(|(elf_id, _)| !elf_id.is_empty())
+        else {
+            inventory_builder.report_issue(InventoryIssue::MalformedLine {
+                line_number,
+                content: input_line.to_string(),
+            });
+            continue;
+        };
+
+        let elf_index = match elf_indices.get(elf_id) {
+            Some(&elf_index) => elf_index,
+            None => {
+                let elf_index = inventory_builder.add_elf(Some(elf_id), line_number);
+                elf_indices.insert(elf_id.to_string(), elf_index);
+                elf_index
+            }
+        };
+
+        // A row with no calories is how an elf carrying nothing is written
+        if calories_str.is_empty() {
+            continue;
+        }
+        match calories_str.parse::<i64>() {
+            Ok(calories) => inventory_builder.add_snack(elf_index, calories, line_number)?,
+            Err(_) => inventory_builder.report_issue(InventoryIssue::MalformedLine {
+                line_number,
+                content: input_line.to_string(),
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_named(
+    input_string: &str,
+    inventory_builder: &mut InventoryBuilder,
+) -> Result<(), InventoryError> {
+    let mut current_elf_index: Option<usize> = None;
+
+    for (line_index, input_line) in input_string.lines().enumerate() {
+        let line_number = line_index + 1;
+        let input_line = input_line.trim();
+        if input_line.is_empty() {
+            continue;
+        }
+
+        if let Some(elf_id) = get_named_elf_header(input_line) {
+            current_elf_index = Some(inventory_builder.add_elf(Some(elf_id), line_number));
+            continue;
+        }
+
+        // Calories before the first header don't belong to any elf, so they're as malformed as "12a"
+        match (current_elf_index, input_line.parse::<i64>()) {
+            (Some(elf_index), Ok(calories)) => {
+                inventory_builder.add_snack(elf_index, calories, line_number)?
+            }
+            _ => inventory_builder.report_issue(InventoryIssue::MalformedLine {
+                line_number,
+                content: input_line.to_string(),
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the one shape of JSON an inventory can be: an array of arrays of numbers.
+/// Tracks the line it's on, for the errors and issues
+struct JsonReader<'a> {
+    input_bytes: &'a [u8],
+    position: usize,
+    line_number: usize,
+}
+
+impl JsonReader<'_> {
+    fn skip_whitespace(&mut self) {
+        while let Some(&input_byte) = self.input_bytes.get(self.position) {
+            match input_byte {
+                b'\n' => self.line_number += 1,
+                b' ' | b'\t' | b'\r' => {}
+                _ => break,
+            }
+            self.position += 1;
+        }
+    }
+
+    /// The next byte after any whitespace, without taking it
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input_bytes.get(self.position).copied()
+    }
+
+    fn syntax_error(&self, message: &str) -> InventoryError {
+        let found = match self.input_bytes.get(self.position) {
+            Some(&input_byte) => format!("'{}'", input_byte as char),
+            None => "the end of the input".to_string(),
+        };
+        InventoryError::Syntax {
+            line_number: self.line_number,
+            message: format!("expected {}, found {}", message, found),
+        }
+    }
+
+    fn expect(&mut self, expected_byte: u8, message: &str) -> Result<(), InventoryError> {
+        if self.peek() != Some(expected_byte) {
+            return Err(self.syntax_error(message));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Everything that could be part of a number, so "1.5" and "2e3" come out whole,
+    /// to be reported as malformed calorie counts rather than syntax errors
+    fn read_number_str(&mut self) -> &str {
+        let number_start = self.position;
+        while let Some(input_byte) = self.input_bytes.get(self.position) {
+            if !matches!(input_byte, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') {
+                break;
+            }
+            self.position += 1;
+        }
+
+        std::str::from_utf8(&self.input_bytes[number_start..self.position])
+            .expect("Number characters are all ASCII")
+    }
+
+    /// Read the items of an array up to its closing bracket, once the opening one has been taken.
+    /// read_item is called at the start of each item
+    fn read_array_items(
+        &mut self,
+        mut read_item: impl FnMut(&mut Self) -> Result<(), InventoryError>,
+    ) -> Result<(), InventoryError> {
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(());
+        }
+
+        loop {
+            read_item(self)?;
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.syntax_error("',' or ']'")),
+            }
+        }
+    }
+}
+
+fn parse_json(
+    input_string: &str,
+    inventory_builder: &mut InventoryBuilder,
+) -> Result<(), InventoryError> {
+    let mut json_reader = JsonReader {
+        input_bytes: input_string.as_bytes(),
+        position: 0,
+        line_number: 1,
+    };
+
+    json_reader.expect(b'[', "'[' to start the inventory")?;
+    json_reader.read_array_items(|json_reader| {
+        let elf_index = inventory_builder.add_elf(None, json_reader.line_number);
+        json_reader.expect(b'[', "'[' to start an elf")?;
+
+        json_reader.read_array_items(|json_reader| {
+            if !matches!(json_reader.peek(), Some(b'0'..=b'9' | b'-')) {
+                return Err(json_reader.syntax_error("a calorie count"));
+            }
+            let line_number = json_reader.line_number;
+            let number_str = json_reader.read_number_str();
+
+            match number_str.parse::<i64>() {
+                Ok(calories) => inventory_builder.add_snack(elf_index, calories, line_number),
+                Err(_) => {
+                    let content = number_str.to_string();
+                    inventory_builder.report_issue(InventoryIssue::MalformedLine {
+                        line_number,
+                        content,
+                    });
+                    Ok(())
+                }
+            }
+        })
+    })?;
+
+    if json_reader.peek().is_some() {
+        return Err(json_reader.syntax_error("nothing after the inventory"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The elves from the puzzle text
+    const EXAMPLE_SNACKS: [&[i64]; 5] = [
+        &[1000, 2000, 3000],
+        &[4000],
+        &[5000, 6000],
+        &[7000, 8000, 9000],
+        &[10000],
+    ];
+
+    fn read_snacks(
+        input_string: &str,
+        input_format: Option<InventoryFormat>,
+    ) -> Result<Vec<Vec<i64>>, InventoryError> {
+        let (elf_inventories, _) = read_inventory(
+            input_string.as_bytes(),
+            input_format,
+            ValidationMode::Strict,
+        )?;
+        Ok(elf_inventories
+            .into_iter()
+            .map(|elf_inventory| elf_inventory.snack_calories)
+            .collect())
+    }
+
+    #[test]
+    fn every_format_reads_the_example() {
+        let csv_input = "elf_id,calories\n1,1000\n1,2000\n1,3000\n2,4000\n3,5000\n3,6000\n\
+                         4,7000\n4,8000\n4,9000\n5,10000\n";
+        let json_input = "[\n  [1000, 2000, 3000],\n  [4000],\n  [5000, 6000],\n  \
+                          [7000, 8000, 9000],\n  [10000]\n]\n";
+        let named_input =
+            "Elf Alice:\n1000\n2000\n3000\n\nElf Bob:\n4000\nElf Carol:\n5000\n6000\n\n\
+                           Elf Dave:\n7000\n8000\n9000\n\nElf Eve:\n10000\n";
+
+        for (input_string, input_format) in [
+            (csv_input, InventoryFormat::Csv),
+            (json_input, InventoryFormat::Json),
+            (named_input, InventoryFormat::Named),
+        ] {
+            assert_eq!(
+                detect_format(input_string.as_bytes()).unwrap().0,
+                input_format
+            );
+            assert_eq!(read_snacks(input_string, None).unwrap(), EXAMPLE_SNACKS);
+        }
+
+        let (elf_inventories, _) =
+            read_inventory(named_input.as_bytes(), None, ValidationMode::Strict).unwrap();
+        assert_eq!(elf_inventories[1].elf_name, Some("Bob".to_string()));
+    }
+
+    /// Hands over one byte per read, like a slow pipe
+    struct OneByteReader<'a>(&'a [u8]);
+
+    impl io::Read for OneByteReader<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let Some((&first_byte, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            if buffer.is_empty() {
+                return Ok(0);
+            }
+
+            buffer[0] = first_byte;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn detection_reads_whole_lines_from_slow_readers() {
+        for (input_string, input_format) in [
+            ("\n\n  \nElf Alice:\n100\n", InventoryFormat::Named),
+            ("\n\nAlice,100\n", InventoryFormat::Csv),
+            ("\n   [[100]]", InventoryFormat::Json),
+            ("\n\n100\n", InventoryFormat::Plain),
+            ("\n\n", InventoryFormat::Plain),
+        ] {
+            let slow_reader = io::BufReader::new(OneByteReader(input_string.as_bytes()));
+            let (detected_format, mut input_reader) = detect_format(slow_reader).unwrap();
+            assert_eq!(detected_format, input_format, "{:?}", input_string);
+
+            // Nothing read to decide is lost
+            let mut read_back = String::new();
+            input_reader.read_to_string(&mut read_back).unwrap();
+            assert_eq!(read_back, input_string);
+        }
+
+        let slow_reader = io::BufReader::new(OneByteReader(b"\nElf Alice:\n100\n"));
+        let (elf_inventories, _) =
+            read_inventory(slow_reader, None, ValidationMode::Strict).unwrap();
+        assert_eq!(elf_inventories[0].elf_name, Some("Alice".to_string()));
+    }
+
+    #[test]
+    fn csv_rows_can_be_in_any_order() {
+        let (elf_inventories, _) = read_inventory(
+            "Alice,100\nBob,\nAlice,200\nCarol, Jr.,300\n".as_bytes(),
+            Some(InventoryFormat::Csv),
+            ValidationMode::Lenient,
+        )
+        .unwrap();
+
+        let elf_snacks: Vec<(Option<&str>, &[i64])> = elf_inventories
+            .iter()
+            .map(|elf_inventory| {
+                (
+                    elf_inventory.elf_name.as_deref(),
+                    elf_inventory.snack_calories.as_slice(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            elf_snacks,
+            vec![
+                (Some("Alice"), &[100, 200][..]),
+                (Some("Bob"), &[][..]),
+                (Some("Carol, Jr."), &[300][..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn issues_are_reported_in_every_format() {
+        let Err(InventoryError::Invalid(inventory_issues)) =
+            read_snacks("Elf Alice:\n100\n12a\nElf Bob:\n\nElf Carol:\n-5\n", None)
+        else {
+            panic!("Strict mode accepted a messy inventory");
+        };
+        assert_eq!(
+            inventory_issues,
+            vec![
+                InventoryIssue::MalformedLine {
+                    line_number: 3,
+                    content: "12a".to_string()
+                },
+                InventoryIssue::EmptyElf {
+                    line_number: 4,
+                    elf_number: 2
+                },
+                InventoryIssue::NegativeCalories {
+                    line_number: 7,
+                    calories: -5
+                },
+            ]
+        );
+
+        let (_, inventory_summary) = read_inventory(
+            "[[1, 1.5],\n [],\n [2e3, -1]]".as_bytes(),
+            None,
+            ValidationMode::Lenient,
+        )
+        .unwrap();
+        assert_eq!(inventory_summary.elf_count, 3);
+        assert_eq!(inventory_summary.ignored.malformed_lines, 2);
+        assert_eq!(inventory_summary.ignored.negative_values, 1);
+        assert_eq!(inventory_summary.ignored.empty_elves, 1);
+    }
+
+    #[test]
+    fn broken_json_gives_syntax_errors() {
+        for (input_string, expected_line_number) in [
+            ("[[1, 2]", 1),
+            ("[[1, 2],\n [3 4]]", 2),
+            ("[[1], \"2\"]", 1),
+            ("[[1]]\n\n]", 3),
+            ("[[1,]]", 1),
+        ] {
+            match read_snacks(input_string, Some(InventoryFormat::Json)) {
+                Err(InventoryError::Syntax { line_number, .. }) => {
+                    assert_eq!(line_number, expected_line_number, "{}", input_string)
+                }
+                other => panic!("{} gave {:?}", input_string, other),
+            }
+        }
+
+        assert_eq!(read_snacks("[]", None).unwrap(), Vec::<Vec<i64>>::new());
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        let elf_inventories: Vec<ElfInventory> = [
+            (Some("Alice"), &[1000, 2000][..]),
+            (None, &[][..]),
+            (Some("Bob, the Elder"), &[-3, 4][..]),
+            (None, &[i64::MAX][..]),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(elf_index, (elf_name, snack_calories))| ElfInventory {
+            elf_number: elf_index + 1,
+            elf_name: elf_name.map(|elf_name| elf_name.to_string()),
+            snack_calories: snack_calories.to_vec(),
+        })
+        .collect();
+
+        for output_format in [
+            InventoryFormat::Plain,
+            InventoryFormat::Csv,
+            InventoryFormat::Json,
+            InventoryFormat::Named,
+        ] {
+            let mut output_bytes: Vec<u8> = Vec::new();
+            write_inventory(&mut output_bytes, &elf_inventories, output_format).unwrap();
+
+            // Detected, rather than given, to check what's written looks like its own format
+            let (read_back, _) =
+                read_inventory(output_bytes.as_slice(), None, ValidationMode::Lenient).unwrap();
+
+            // Only the CSV and named formats keep names
+            let keeps_names =
+                matches!(output_format, InventoryFormat::Csv | InventoryFormat::Named);
+            for (original, read_back) in elf_inventories.iter().zip(&read_back) {
+                assert_eq!(read_back.snack_calories, original.snack_calories);
+                assert_eq!(read_back.elf_number, original.elf_number);
+                if keeps_names {
+                    assert_eq!(read_back.elf_name, original.elf_name);
+                }
+            }
+            assert_eq!(read_back.len(), elf_inventories.len(), "{}", output_format);
+        }
+    }
+
+    #[test]
+    fn numeric_ids_are_elf_numbers() {
+        let (elf_inventories, _) = read_inventory(
+            "7,100\n3,200\nAlice,300\n7,400\n".as_bytes(),
+            None,
+            ValidationMode::Strict,
+        )
+        .unwrap();
+
+        // Alice takes the lowest number the numbered elves don't have
+        let elf_ids: Vec<(usize, Option<&str>)> = elf_inventories
+            .iter()
+            .map(|elf_inventory| (elf_inventory.elf_number, elf_inventory.elf_name.as_deref()))
+            .collect();
+        assert_eq!(elf_ids, vec![(7, None), (3, None), (1, Some("Alice"))]);
+        assert_eq!(elf_inventories[0].snack_calories, vec![100, 400]);
+
+        // Converting keeps every ID, in both formats that have them
+        for output_format in [InventoryFormat::Csv, InventoryFormat::Named] {
+            let mut output_bytes: Vec<u8> = Vec::new();
+            write_inventory(&mut output_bytes, &elf_inventories, output_format).unwrap();
+            let (read_back, _) =
+                read_inventory(output_bytes.as_slice(), None, ValidationMode::Strict).unwrap();
+
+            assert_eq!(read_back, elf_inventories, "{}", output_format);
+        }
+    }
+
+    #[test]
+    fn repeated_ids_are_issues() {
+        let named_input = "Elf 7:\n1\nElf Alice:\n2\nElf 7:\n3\nElf Alice:\n4\n";
+
+        let Err(InventoryError::Invalid(inventory_issues)) =
+            read_snacks(named_input, Some(InventoryFormat::Named))
+        else {
+            panic!("Strict mode accepted repeated elf IDs");
+        };
+        assert_eq!(
+            inventory_issues,
+            vec![
+                InventoryIssue::RepeatedElfId {
+                    line_number: 5,
+                    elf_id: "7".to_string()
+                },
+                InventoryIssue::RepeatedElfId {
+                    line_number: 7,
+                    elf_id: "Alice".to_string()
+                },
+            ]
+        );
+
+        // Lenient mode keeps every elf, and gives the repeated 7 a number of its own
+        let (elf_inventories, inventory_summary) =
+            read_inventory(named_input.as_bytes(), None, ValidationMode::Lenient).unwrap();
+        let elf_numbers: Vec<usize> = elf_inventories
+            .iter()
+            .map(|elf_inventory| elf_inventory.elf_number)
+            .collect();
+        assert_eq!(elf_numbers, vec![7, 1, 2, 3]);
+        assert_eq!(inventory_summary.ignored.repeated_elf_ids, 2);
+
+        // A numbered elf's number isn't taken by a named elf that comes first
+        let (elf_inventories, _) =
+            read_inventory("Alice,1\n1,2\n".as_bytes(), None, ValidationMode::Strict).unwrap();
+        assert_eq!(elf_inventories[0].elf_number, 2);
+        assert_eq!(elf_inventories[1].elf_number, 1);
+    }
+
+    #[test]
+    fn format_names() {
+        assert_eq!("CSV".parse::<InventoryFormat>(), Ok(InventoryFormat::Csv));
+        assert_eq!(
+            InventoryFormat::Named
+                .to_string()
+                .parse::<InventoryFormat>(),
+            Ok(InventoryFormat::Named)
+        );
+        assert!("xml".parse::<InventoryFormat>().is_err());
+    }
+}
@@ -0,0 +1,323 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+};
+
+use log::{info, trace};
+
+use crate::utils::ElfTotal;
+
+/// How to treat lines that don't fit the inventory format
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ValidationMode {
+    /// Every issue is an error. All of them are collected and reported together at the end
+    Strict,
+    /// Skip malformed lines, accept negative calories, and count empty elves as carrying 0 calories.
+    /// Issues are only counted, for a summary
+    Lenient,
+}
+
+/// Something wrong with one line of an inventory. Line numbers count from 1
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InventoryIssue {
+    /// A line that isn't a whole number, like "12a" or "1.5"
+    MalformedLine { line_number: usize, content: String },
+    /// Elves can't carry less than nothing
+    NegativeCalories { line_number: usize, calories: i64 },
+    /// A blank line ended a group with no calorie lines in it, like the second of two blank lines in a row
+    EmptyElf {
+        line_number: usize,
+        elf_number: usize,
+    },
+    /// An elf whose number or name is already used by an earlier elf. Only formats that give elves IDs can have this.
+    /// Rows for the same elf in a CSV inventory are all one elf, so there it's a named elf's number clashing
+    RepeatedElfId { line_number: usize, elf_id: String },
+}
+
+impl fmt::Display for InventoryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedLine {
+                line_number,
+                content,
+            } => write!(
+                f,
+                "line {}: \"{}\" is not a calorie count",
+                line_number, content
+            ),
+            Self::NegativeCalories {
+                line_number,
+                calories,
+            } => write!(f, "line {}: negative calories {}", line_number, calories),
+            Self::EmptyElf {
+                line_number,
+                elf_number,
+            } => write!(
+                f,
+                "line {}: elf {} has no calorie lines",
+                line_number, elf_number
+            ),
+            Self::RepeatedElfId {
+                line_number,
+                elf_id,
+            } => write!(
+                f,
+                "line {}: elf ID {} is already used by another elf",
+                line_number, elf_id
+            ),
+        }
+    }
+}
+
+/// What a lenient read let through. Only counts are kept, so this stays small however bad the input is
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct IgnoredCounts {
+    pub malformed_lines: usize,
+    pub negative_values: usize,
+    pub empty_elves: usize,
+    pub repeated_elf_ids: usize,
+}
+
+impl IgnoredCounts {
+    pub fn count(&mut self, inventory_issue: &InventoryIssue) {
+        match inventory_issue {
+            InventoryIssue::MalformedLine { .. } => self.malformed_lines += 1,
+            InventoryIssue::NegativeCalories { .. } => self.negative_values += 1,
+            InventoryIssue::EmptyElf { .. } => self.empty_elves += 1,
+            InventoryIssue::RepeatedElfId { .. } => self.repeated_elf_ids += 1,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == IgnoredCounts::default()
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct InventorySummary {
+    pub elf_count: usize,
+    pub line_count: usize,
+    /// Always empty for a strict read, which fails instead
+    pub ignored: IgnoredCounts,
+}
+
+#[derive(Debug)]
+pub enum InventoryError {
+    Io(io::Error),
+    /// An elf's running total no longer fits in 64 bits. Holds the elf number, and the line that pushed it over
+    ElfOverflow {
+        elf_number: usize,
+        line_number: usize,
+    },
+    /// The elves being added together carry more than fits in 64 bits between them
+    CombinedOverflow,
+    /// A strict read found issues. Holds every one of them, in line order
+    Invalid(Vec<InventoryIssue>),
+    /// The input doesn't follow its format at all, like a JSON inventory with a missing bracket.
+    /// Unlike an issue, this stops the read in both validation modes
+    Syntax {
+        line_number: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read inventory: {}", err),
+            Self::ElfOverflow {
+                elf_number,
+                line_number,
+            } => write!(
+                f,
+                "calories for elf {} overflow 64 bits at line {}",
+                elf_number, line_number
+            ),
+            Self::CombinedOverflow => write!(f, "combined calories overflow 64 bits"),
+            Self::Invalid(inventory_issues) => {
+                write!(f, "inventory has {} issues", inventory_issues.len())?;
+                for inventory_issue in inventory_issues {
+                    write!(f, "\n  {}", inventory_issue)?;
+                }
+                Ok(())
+            }
+            Self::Syntax {
+                line_number,
+                message,
+            } => write!(f, "line {}: {}", line_number, message),
+        }
+    }
+}
+
+impl std::error::Error for InventoryError {}
+
+impl From<io::Error> for InventoryError {
+    fn from(err: io::Error) -> Self {
+        InventoryError::Io(err)
+    }
+}
+
+/// One elf's snacks, in the order they were listed
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ElfInventory {
+    pub elf_number: usize,
+    /// Only some formats name their elves, like "Elf Alice:" blocks. See formats.rs
+    pub elf_name: Option<String>,
+    pub snack_calories: Vec<i64>,
+}
+
+impl ElfInventory {
+    /// Every reader checks each elf's total fits in 64 bits, so adding it up again can't overflow
+    pub fn get_elf_total(&self) -> ElfTotal {
+        ElfTotal {
+            elf_number: self.elf_number,
+            total_calories: self.snack_calories.iter().sum(),
+        }
+    }
+}
+
+/// Read an inventory one elf at a time, and hand each elf's total and snacks to handle_elf as soon as its group ends.
+///
+/// Only the current line and the current elf's snacks are held, and both buffers are reused,
+/// so memory use stays the same however long the input is. Works on anything buffered: a file, or stdin.
+/// The one exception is a strict read of a broken inventory, which keeps every issue so it can report them all
+pub fn for_each_elf<R: BufRead>(
+    mut reader: R,
+    validation_mode: ValidationMode,
+    mut handle_elf: impl FnMut(ElfTotal, &[i64]),
+) -> Result<InventorySummary, InventoryError> {
+    let mut input_line = String::new();
+    let mut inventory_summary = InventorySummary::default();
+    let mut inventory_issues: Vec<InventoryIssue> = Vec::new();
+    let mut current_elf_calorie_count: i64 = 0;
+    let mut current_elf_snacks: Vec<i64> = Vec::new();
+
+    let mut report_issue = |inventory_issue: InventoryIssue| {
+        trace!("Inventory issue: {}", inventory_issue);
+        match validation_mode {
+            ValidationMode::Strict => inventory_issues.push(inventory_issue),
+            ValidationMode::Lenient => inventory_summary.ignored.count(&inventory_issue),
+        }
+    };
+
+    info!(" Begin calorie computation!");
+
+    let mut line_number = 0;
+    let mut elf_count = 0;
+    loop {
+        input_line.clear();
+        if reader.read_line(&mut input_line)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let input_line = input_line.trim();
+        if input_line.is_empty() {
+            // This is a blank line.
+            // Total calories for the previous elf counted. Time to hand it over.
+            elf_count += 1;
+            if current_elf_snacks.is_empty() {
+                report_issue(InventoryIssue::EmptyElf {
+                    line_number,
+                    elf_number: elf_count,
+                });
+            }
+            trace!(
+                "Encountered blank line! Total calories = {} for elf {}",
+                current_elf_calorie_count,
+                elf_count
+            );
+            handle_elf(
+                ElfTotal {
+                    elf_number: elf_count,
+                    total_calories: current_elf_calorie_count,
+                },
+                &current_elf_snacks,
+            );
+
+            // Reset the count so that we can start counting calories for the next elf.
+            current_elf_calorie_count = 0;
+            current_elf_snacks.clear();
+            continue;
+        }
+
+        let Ok(calories) = input_line.parse::<i64>() else {
+            // Skipped in lenient mode, as it always has been
+            report_issue(InventoryIssue::MalformedLine {
+                line_number,
+                content: input_line.to_string(),
+            });
+            continue;
+        };
+
+        if calories < 0 {
+            report_issue(InventoryIssue::NegativeCalories {
+                line_number,
+                calories,
+            });
+        }
+
+        current_elf_snacks.push(calories);
+        current_elf_calorie_count =
+            current_elf_calorie_count
+                .checked_add(calories)
+                .ok_or(InventoryError::ElfOverflow {
+                    elf_number: elf_count + 1,
+                    line_number,
+                })?;
+    }
+
+    // Handle scenario where the last line of the input is not a blank line.
+    // Hand over total calories for the last elf, even if they come to 0 or less
+    if !current_elf_snacks.is_empty() {
+        elf_count += 1;
+        trace!(
+            "Current Calorie Count = {}. Adding entry for the last elf",
+            current_elf_calorie_count
+        );
+        handle_elf(
+            ElfTotal {
+                elf_number: elf_count,
+                total_calories: current_elf_calorie_count,
+            },
+            &current_elf_snacks,
+        );
+    }
+
+    info!("Read {} elves from {} lines", elf_count, line_number);
+    if !inventory_issues.is_empty() {
+        return Err(InventoryError::Invalid(inventory_issues));
+    }
+
+    inventory_summary.elf_count = elf_count;
+    inventory_summary.line_count = line_number;
+    Ok(inventory_summary)
+}
+
+/// Read the whole of a plain inventory into memory, keeping every elf's snacks
+pub fn read_elves<R: BufRead>(
+    reader: R,
+    validation_mode: ValidationMode,
+) -> Result<(Vec<ElfInventory>, InventorySummary), InventoryError> {
+    let mut elf_inventories: Vec<ElfInventory> = Vec::new();
+    let inventory_summary = for_each_elf(reader, validation_mode, |elf_total, snack_calories| {
+        elf_inventories.push(ElfInventory {
+            elf_number: elf_total.elf_number,
+            elf_name: None,
+            snack_calories: snack_calories.to_vec(),
+        })
+    })?;
+
+    Ok((elf_inventories, inventory_summary))
+}
+
+/// Add up the elves' totals, failing instead of wrapping around if they don't fit
+pub fn get_combined_calories(elf_totals: &[ElfTotal]) -> Result<i64, InventoryError> {
+    elf_totals
+        .iter()
+        .try_fold(0i64, |combined_calories, elf_total| {
+            combined_calories
+                .checked_add(elf_total.total_calories)
+                .ok_or(InventoryError::CombinedOverflow)
+        })
+}
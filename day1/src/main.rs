@@ -1,95 +1,653 @@
 use log::info;
 use std::{
     fs::File,
-    io::{BufRead, BufReader},
+    io::{self, BufRead, BufReader},
+    time::Instant,
 };
 
-fn main() {
-    // This will hold the total calories carried by each elf.
-    // The [vector index + 1] will denote the "name" of the elf.
-    // Example:- If we want the number of calories carried by Elf 5, we will get elf_total_calories[4]
-    let mut elf_total_calories: Vec<i32> = vec![];
-    // Input file reader
-    let input_file_lines =
-        BufReader::new(File::open("input.txt").expect("Failed to read input file")).lines();
+mod formats;
+mod inventory;
+mod planner;
+mod stats;
+mod utils;
+use formats::{read_inventory, resolve_format, write_inventory, InventoryFormat};
+use inventory::{
+    for_each_elf, get_combined_calories, ElfInventory, InventoryError, InventorySummary,
+    ValidationMode,
+};
+use planner::{plan_load_balance, LoadPlan};
+use stats::{get_histogram, get_statistics, render_histogram, Statistics};
+use utils::{ElfTotal, TopElves};
+
+const INPUT_FILENAME: &str = "input.txt";
 
-    let mut current_elf_calorie_count = 0;
+/// How many elves --stream reports when --top isn't given
+const DEFAULT_STREAM_TOP_COUNT: usize = 3;
 
+fn main() {
     env_logger::init();
-    info!(" Begin calorie computation!");
-
-    // Go through each line of the input
-    for input_line_result in input_file_lines {
-        if let Ok(input_line) = input_line_result {
-            // We are counting bytes here. But an empty line should have zero bytes between two newline chars.
-            // So a check against zero should work
-            if input_line.trim().len() == 0 {
-                // This is a blank line.
-                // Total calories for the previous elf counted. Time to push this to the vector.
-                info!(
-                    "Encountered blank line! Total calories = {} for elf {}",
-                    current_elf_calorie_count,
-                    elf_total_calories.len() + 1
-                );
-                elf_total_calories.push(current_elf_calorie_count);
-
-                // Reset the count so that we can start counting calories for the next elf.
-                info!("Resetting calorie counts to zero");
-                current_elf_calorie_count = 0;
-
-                // Read next line from input file
-                continue;
-            } else {
-                // Convert string to integer
-                // Turbofish :)
-                let current_line_parsed = input_line.parse::<i32>();
-
-                // Check for parsing error
-                if let Ok(calories) = current_line_parsed {
-                    //Add the calories on the current line to the total calorie count of the elf
-                    info!(
-                        "Current Calorie Count = {}. Adding {}",
-                        current_elf_calorie_count, calories
-                    );
-                    current_elf_calorie_count += calories
-                }
-            }
-        } else {
-            panic!("Failed to read input lines! Exiting program!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    // --strict rejects any inventory with malformed lines, negative calories or empty elves, and lists them all.
+    // Without it, they're handled the way they always have been, with a summary of what was let through
+    let validation_mode = if std::env::args().any(|arg| arg == "--strict") {
+        ValidationMode::Strict
+    } else {
+        ValidationMode::Lenient
+    };
+
+    // Plain, CSV, JSON or named elves. Detected from the input, unless --format says which it is
+    let input_format = get_arg_or_exit(get_format_arg("--format"));
+    // Read up front, so a bad value is reported before any work is done
+    let output_format = get_arg_or_exit(get_format_arg("--convert"));
+    let top_count = get_arg_or_exit(get_arg_value("--top"));
+    let group_count = get_arg_or_exit(get_arg_value("--groups"));
+
+    // Write the inventory out in another format, like "--convert csv".
+    // Reads --stream's file, or stdin, if given. Example: "--stream elves.json --convert named > elves.txt"
+    if let Some(output_format) = output_format {
+        let input_source = get_stream_source().unwrap_or_else(|| INPUT_FILENAME.to_string());
+
+        if let Err(err) = open_input(&input_source)
+            .and_then(|input_reader| read_inventory(input_reader, input_format, validation_mode))
+            .and_then(|(elf_inventories, _)| {
+                Ok(write_inventory(
+                    io::stdout().lock(),
+                    &elf_inventories,
+                    output_format,
+                )?)
+            })
+        {
+            eprintln!("Could not convert inventory from {}: {}", input_source, err);
+            std::process::exit(1);
         }
-    } //for loop
-
-    // Handle scenario where the last line of the input is not a blank line.
-    // Add total calories for the last elf to the vector
-    if current_elf_calorie_count > 0 {
-        info!(
-            "Current Calorie Count = {}. Adding entry for the last elf",
-            current_elf_calorie_count
-        );
-        elf_total_calories.push(current_elf_calorie_count);
+        return;
     }
 
-    // Sort the vector in reverse order
-    // https://doc.rust-lang.org/std/vec/struct.Vec.html#examples-153
-    elf_total_calories.sort_by(|a, b| b.cmp(a));
+    // Streaming mode: read any file, or stdin, and only report the top elves.
+    // Examples: "--stream huge.txt --top 10" and "cat huge.txt | day1 --stream"
+    if let Some(stream_source) = get_stream_source() {
+        let top_count = top_count.unwrap_or(DEFAULT_STREAM_TOP_COUNT);
+
+        if let Err(err) = open_input(&stream_source)
+            .and_then(|input_reader| {
+                get_top_elves(input_reader, top_count, input_format, validation_mode)
+            })
+            .and_then(|(top_elves, inventory_summary)| {
+                print_top_elves(&top_elves)?;
+                print_ignored_summary(&inventory_summary);
+                Ok(())
+            })
+        {
+            eprintln!("Could not stream inventory from {}: {}", stream_source, err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let ((part1_answer, part2_answer), inventory_summary) =
+        match solve(INPUT_FILENAME, input_format, validation_mode) {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("Could not count calories in {}: {}", INPUT_FILENAME, err);
+                std::process::exit(1);
+            }
+        };
 
     // Solution to Part 1
     println!(
         "Find the Elf carrying the most Calories. How many total Calories is that Elf carrying? Answer = {} calories",
-        elf_total_calories[0]
+        part1_answer
     );
 
     // Solution to Part 2
-    // To find the total calories carried out by the three elves carrying them,
-    // let's sort the vector and sum the last three values
+    println!(
+        "How many Calories are those Elves carrying in total? Answer = {} calories",
+        part2_answer
+    );
+    print_ignored_summary(&inventory_summary);
+
+    // The N heaviest elves, and which elves they are
+    if let Some(top_count) = top_count {
+        println!();
+        if let Err(err) = open_input(INPUT_FILENAME)
+            .and_then(|input_reader| {
+                get_top_elves(input_reader, top_count, input_format, validation_mode)
+            })
+            .and_then(|(top_elves, _)| print_top_elves(&top_elves))
+        {
+            eprintln!("Could not count calories in {}: {}", INPUT_FILENAME, err);
+            std::process::exit(1);
+        }
+    }
+
+    // Statistics for the elves' totals and for each snack, and a histogram of the totals.
+    // Every elf's snacks are kept for this, unlike the answers above
+    if std::env::args().any(|arg| arg == "--stats") {
+        println!();
+        match open_input(INPUT_FILENAME)
+            .and_then(|input_reader| read_inventory(input_reader, input_format, validation_mode))
+        {
+            Ok((elf_inventories, _)) => print_calorie_statistics(&elf_inventories),
+            Err(err) => {
+                eprintln!("Could not count calories in {}: {}", INPUT_FILENAME, err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Hand snacks around so the heaviest load is as light as possible.
+    // "--balance" evens out the elves, and "--balance --groups 3" evens out three groups of consecutive elves
+    if std::env::args().any(|arg| arg == "--balance") {
+        println!();
+        match open_input(INPUT_FILENAME)
+            .and_then(|input_reader| read_inventory(input_reader, input_format, validation_mode))
+            // Loads are added up in 128 bits, so snacks that don't fit in 64 bits all together still balance
+            .map(|(elf_inventories, _)| elf_inventories)
+        {
+            Ok(elf_inventories) => {
+                print_load_plan(
+                    &plan_load_balance(&elf_inventories, group_count),
+                    group_count,
+                );
+            }
+            Err(err) => {
+                eprintln!("Could not count calories in {}: {}", INPUT_FILENAME, err);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// The flag's value, or print what's wrong with it and exit like any other error in main
+fn get_arg_or_exit<T>(arg_result: Result<Option<T>, String>) -> Option<T> {
+    arg_result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    })
+}
 
-    info!(
-        "Last three values in order {} {} {}",
-        elf_total_calories[0], elf_total_calories[1], elf_total_calories[2]
+/// Read the value following a flag, like the csv in "--format csv". None if the flag isn't given
+fn get_arg_str(flag: &str) -> Result<Option<String>, String> {
+    let args: Vec<String> = std::env::args().collect();
+
+    let Some(flag_index) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    match args.get(flag_index + 1) {
+        Some(value_str) => Ok(Some(value_str.clone())),
+        None => Err(format!("{} needs a value", flag)),
+    }
+}
+
+/// Read the number following a flag, like the 5 in "--top 5"
+fn get_arg_value(flag: &str) -> Result<Option<usize>, String> {
+    let Some(value_str) = get_arg_str(flag)? else {
+        return Ok(None);
+    };
+
+    value_str
+        .parse::<usize>()
+        .map(Some)
+        .map_err(|_| format!("Could not parse {} value {}", flag, value_str))
+}
+
+/// Read the inventory format following a flag, like the json in "--convert json"
+fn get_format_arg(flag: &str) -> Result<Option<InventoryFormat>, String> {
+    let Some(format_str) = get_arg_str(flag)? else {
+        return Ok(None);
+    };
+
+    format_str
+        .parse::<InventoryFormat>()
+        .map(Some)
+        .map_err(|err| format!("Could not parse {} value: {}", flag, err))
+}
+
+/// The file to read after --stream. "-", or no file at all, means stdin
+fn get_stream_source() -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+
+    let flag_index = args.iter().position(|arg| arg == "--stream")?;
+    match args.get(flag_index + 1) {
+        Some(source) if !source.starts_with("--") => Some(source.clone()),
+        _ => Some("-".to_string()),
+    }
+}
+
+/// A buffered reader over the file, or over stdin for "-"
+fn open_input(input_source: &str) -> Result<Box<dyn BufRead>, InventoryError> {
+    if input_source == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(input_source)?)))
+    }
+}
+
+/// Run the calorie computation on the given input file, and return the answers to both parts,
+/// along with a summary of the inventory
+fn solve(
+    input_file_name: &str,
+    input_format: Option<InventoryFormat>,
+    validation_mode: ValidationMode,
+) -> Result<((i64, i64), InventorySummary), InventoryError> {
+    // Solution to Part 1 is the heaviest elf. Solution to Part 2 is the three heaviest elves together.
+    // With fewer than three elves, that's however many there are
+    let (top_elves, inventory_summary) = get_top_elves(
+        open_input(input_file_name)?,
+        3,
+        input_format,
+        validation_mode,
+    )?;
+    info!("Top three elves: {:?}", top_elves);
+
+    let part1_answer = top_elves
+        .first()
+        .map(|elf_total| elf_total.total_calories)
+        .unwrap_or(0);
+    let part2_answer = get_combined_calories(&top_elves)?;
+
+    Ok(((part1_answer, part2_answer), inventory_summary))
+}
+
+/// The N elves carrying the most calories, heaviest first, along with their elf numbers.
+/// A plain inventory is streamed through, so only those N elves are ever held in memory.
+/// The other formats are read whole first
+fn get_top_elves<R: BufRead>(
+    input_reader: R,
+    top_count: usize,
+    input_format: Option<InventoryFormat>,
+    validation_mode: ValidationMode,
+) -> Result<(Vec<ElfTotal>, InventorySummary), InventoryError> {
+    let (input_format, input_reader) = resolve_format(input_reader, input_format)?;
+
+    let mut top_elves = TopElves::new(top_count);
+    let inventory_summary = if input_format == InventoryFormat::Plain {
+        for_each_elf(input_reader, validation_mode, |elf_total, _| {
+            top_elves.push(elf_total)
+        })?
+    } else {
+        let (elf_inventories, inventory_summary) =
+            read_inventory(input_reader, Some(input_format), validation_mode)?;
+        for elf_inventory in &elf_inventories {
+            top_elves.push(elf_inventory.get_elf_total());
+        }
+        inventory_summary
+    };
+
+    Ok((top_elves.into_sorted_vec(), inventory_summary))
+}
+
+fn print_top_elves(top_elves: &[ElfTotal]) -> Result<(), InventoryError> {
+    println!("Top {} elves by calories carried:", top_elves.len());
+    for elf_total in top_elves {
+        println!(
+            "Elf {}: {} calories",
+            elf_total.elf_number, elf_total.total_calories
+        );
+    }
+    println!("Combined: {} calories", get_combined_calories(top_elves)?);
+
+    Ok(())
+}
+
+/// Say what a lenient read let through, if anything
+fn print_ignored_summary(inventory_summary: &InventorySummary) {
+    let ignored = &inventory_summary.ignored;
+    if ignored.is_empty() {
+        return;
+    }
+
+    println!(
+        "\nRead {} elves from {} lines. Skipped {} malformed lines, counted {} negative calorie values, \
+         and counted {} empty elves as carrying 0 calories. Run with --strict to list them",
+        inventory_summary.elf_count,
+        inventory_summary.line_count,
+        ignored.malformed_lines,
+        ignored.negative_values,
+        ignored.empty_elves
     );
-    let result = elf_total_calories[0] + elf_total_calories[1] + elf_total_calories[2];
+    if ignored.repeated_elf_ids > 0 {
+        println!(
+            "Found {} elves with an ID already used by another elf. Repeated numbers were replaced with unused ones",
+            ignored.repeated_elf_ids
+        );
+    }
+}
+
+fn print_calorie_statistics(elf_inventories: &[ElfInventory]) {
+    let elf_totals: Vec<i64> = elf_inventories
+        .iter()
+        .map(|elf_inventory| elf_inventory.get_elf_total().total_calories)
+        .collect();
+    let snack_calories: Vec<i64> = elf_inventories
+        .iter()
+        .flat_map(|elf_inventory| elf_inventory.snack_calories.iter().copied())
+        .collect();
+    let snack_counts: Vec<i64> = elf_inventories
+        .iter()
+        .map(|elf_inventory| elf_inventory.snack_calories.len() as i64)
+        .collect();
+
+    print_statistics("Calories carried by each elf", get_statistics(&elf_totals));
+    print_statistics("Calories in each snack", get_statistics(&snack_calories));
+    print_statistics("Snacks carried by each elf", get_statistics(&snack_counts));
+
+    println!("Histogram of calories carried by each elf:");
+    for histogram_line in render_histogram(&get_histogram(&elf_totals)) {
+        println!("  {}", histogram_line);
+    }
+}
+
+fn print_statistics(title: &str, statistics: Option<Statistics>) {
+    println!("{}:", title);
+    let Some(statistics) = statistics else {
+        println!("  Nothing to count\n");
+        return;
+    };
+
     println!(
-        "How many Calories are those Elves carrying in total? Answer = {} calories",
-        result
+        "  Count: {}  Total: {}  Min: {}  Max: {}",
+        statistics.count, statistics.total, statistics.min, statistics.max
+    );
+    println!(
+        "  Mean: {:.1}  Median: {:.1}  Standard deviation: {:.1}",
+        statistics.mean, statistics.median, statistics.std_dev
+    );
+    let percentile_strs: Vec<String> = statistics
+        .percentiles
+        .iter()
+        .map(|(percentile, value)| format!("p{}: {:.1}", percentile, value))
+        .collect();
+    println!("  Percentiles: {}\n", percentile_strs.join("  "));
+}
+
+fn print_load_plan(load_plan: &LoadPlan, group_count: Option<usize>) {
+    let (carrier_name, carriers_name) = if group_count.is_some() {
+        ("group", "groups")
+    } else {
+        ("elf", "elves")
+    };
+
+    println!(
+        "Load balancing plan across {} {}:",
+        load_plan.carrier_count, carriers_name
+    );
+    println!(
+        "Heaviest load before: {} calories, carried by {} {}",
+        load_plan.max_load_before.1, carrier_name, load_plan.max_load_before.0
+    );
+    println!(
+        "Heaviest load after: {} calories, carried by {} {}",
+        load_plan.max_load_after.1, carrier_name, load_plan.max_load_after.0
     );
+    println!(
+        "No plan can do better than: {} calories",
+        load_plan.lower_bound
+    );
+    if load_plan.is_best_possible {
+        println!("This plan is the best possible");
+    } else {
+        println!("This plan is the best found, but it isn't proven to be the best possible");
+    }
+
+    // Snacks stay with their elf wherever the heaviest load allows, but nothing proves fewer moves can't do it
+    println!(
+        "Snacks to move: {} (as few as were found, not proven to be the fewest)",
+        load_plan.moves.len()
+    );
+    for snack_move in &load_plan.moves {
+        if group_count.is_some() {
+            println!(
+                "  Elf {} in group {} gives a {} calorie snack to group {}",
+                snack_move.from_elf,
+                snack_move.from_carrier,
+                snack_move.snack_calories,
+                snack_move.to_carrier
+            );
+        } else {
+            println!(
+                "  Elf {} gives a {} calorie snack to elf {}",
+                snack_move.from_elf, snack_move.snack_calories, snack_move.to_carrier
+            );
+        }
+    }
+}
+
+/// Print the answers, and the time taken to compute them, in the format read by the report crate.
+/// Both parts are computed in the same pass, so they share a single timing
+fn print_report(input_file_name: &str) {
+    let solve_start = Instant::now();
+    let ((part1_answer, part2_answer), _) =
+        solve(input_file_name, None, ValidationMode::Lenient).expect("Could not count calories");
+    let solve_time = solve_start.elapsed();
+
+    println!("answer\tPart 1\t{}", part1_answer);
+    println!("answer\tPart 2\t{}", part2_answer);
+    println!("timing\tBoth parts\t{}", solve_time.as_nanos());
+
+    let (elf_inventories, _) = open_input(input_file_name)
+        .and_then(|input_reader| read_inventory(input_reader, None, ValidationMode::Lenient))
+        .expect("Could not read inventory");
+    let elf_totals: Vec<i64> = elf_inventories
+        .iter()
+        .map(|elf_inventory| elf_inventory.get_elf_total().total_calories)
+        .collect();
+
+    println!("bars\tElves carrying each range of calories");
+    for histogram_bin in get_histogram(&elf_totals) {
+        println!(
+            "0\t{} - {}\t{}",
+            histogram_bin.lower, histogram_bin.upper, histogram_bin.count
+        );
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inventory::{IgnoredCounts, InventoryIssue};
+
+    #[test]
+    fn example_answers() {
+        // The example is a clean inventory, so strict mode accepts it
+        assert_eq!(
+            solve("example.txt", None, ValidationMode::Strict)
+                .unwrap()
+                .0,
+            (24000, 45000)
+        );
+    }
+
+    #[test]
+    fn example_top_elves_keep_their_numbers() {
+        let top_elves = get_top_elves(
+            open_input("example.txt").unwrap(),
+            3,
+            None,
+            ValidationMode::Strict,
+        )
+        .unwrap()
+        .0;
+
+        // The puzzle text's fourth, third and fifth elves
+        assert_eq!(
+            top_elves,
+            vec![
+                ElfTotal {
+                    elf_number: 4,
+                    total_calories: 24000
+                },
+                ElfTotal {
+                    elf_number: 3,
+                    total_calories: 11000
+                },
+                ElfTotal {
+                    elf_number: 5,
+                    total_calories: 10000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fewer_than_three_elves() {
+        let top_elves = get_top_elves("2000\n\n5000\n".as_bytes(), 3, None, ValidationMode::Strict)
+            .unwrap()
+            .0;
+
+        assert_eq!(top_elves.len(), 2);
+        assert_eq!(top_elves[0].elf_number, 2);
+    }
+
+    #[test]
+    fn overflow_is_reported_not_wrapped() {
+        let overflowing_elf = format!("1\n\n{}\n1\n", i64::MAX);
+        assert!(matches!(
+            get_top_elves(overflowing_elf.as_bytes(), 3, None, ValidationMode::Lenient),
+            Err(InventoryError::ElfOverflow {
+                elf_number: 2,
+                line_number: 4
+            })
+        ));
+
+        // Each elf fits, but not both together
+        let overflowing_pair = format!("{}\n\n{}\n", i64::MAX, i64::MAX);
+        let top_elves = get_top_elves(
+            overflowing_pair.as_bytes(),
+            2,
+            None,
+            ValidationMode::Lenient,
+        )
+        .unwrap()
+        .0;
+        assert!(matches!(
+            get_combined_calories(&top_elves),
+            Err(InventoryError::CombinedOverflow)
+        ));
+    }
+
+    #[test]
+    fn example_elves_keep_their_snacks() {
+        let (elf_inventories, _) = read_inventory(
+            open_input("example.txt").unwrap(),
+            None,
+            ValidationMode::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(elf_inventories.len(), 5);
+        assert_eq!(
+            elf_inventories[3],
+            ElfInventory {
+                elf_number: 4,
+                elf_name: None,
+                snack_calories: vec![7000, 8000, 9000]
+            }
+        );
+
+        let snack_calories: Vec<i64> = elf_inventories
+            .iter()
+            .flat_map(|elf_inventory| elf_inventory.snack_calories.clone())
+            .collect();
+        assert_eq!(
+            snack_calories,
+            (1..=10).map(|n| n * 1000).collect::<Vec<i64>>()
+        );
+
+        let snack_statistics = get_statistics(&snack_calories).unwrap();
+        assert_eq!(snack_statistics.mean, 5500.0);
+        assert_eq!(snack_statistics.median, 5500.0);
+    }
+
+    /// Malformed, negative, and two blank lines in a row
+    const MESSY_INVENTORY: &str = "100\n12a\n\n\n-50\n200\n\n1.5\n300\n";
+
+    #[test]
+    fn strict_mode_lists_every_issue() {
+        let Err(InventoryError::Invalid(inventory_issues)) =
+            get_top_elves(MESSY_INVENTORY.as_bytes(), 3, None, ValidationMode::Strict)
+        else {
+            panic!("Strict mode accepted a messy inventory");
+        };
+
+        assert_eq!(
+            inventory_issues,
+            vec![
+                InventoryIssue::MalformedLine {
+                    line_number: 2,
+                    content: "12a".to_string()
+                },
+                InventoryIssue::EmptyElf {
+                    line_number: 4,
+                    elf_number: 2
+                },
+                InventoryIssue::NegativeCalories {
+                    line_number: 5,
+                    calories: -50
+                },
+                InventoryIssue::MalformedLine {
+                    line_number: 8,
+                    content: "1.5".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lenient_mode_keeps_old_behaviour_and_counts_issues() {
+        let (top_elves, inventory_summary) =
+            get_top_elves(MESSY_INVENTORY.as_bytes(), 4, None, ValidationMode::Lenient).unwrap();
+
+        // The empty group is still an elf carrying 0, and the negative line still counts
+        let elf_totals: Vec<(usize, i64)> = top_elves
+            .iter()
+            .map(|elf_total| (elf_total.elf_number, elf_total.total_calories))
+            .collect();
+        assert_eq!(elf_totals, vec![(4, 300), (3, 150), (1, 100), (2, 0)]);
+
+        assert_eq!(inventory_summary.elf_count, 4);
+        assert_eq!(inventory_summary.line_count, 9);
+        assert_eq!(
+            inventory_summary.ignored,
+            IgnoredCounts {
+                malformed_lines: 2,
+                negative_values: 1,
+                empty_elves: 1,
+                repeated_elf_ids: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn last_elf_is_kept_whatever_their_total() {
+        // No blank line after the last elf, who carries nothing or less than nothing
+        let (top_elves, inventory_summary) =
+            get_top_elves("100\n\n0\n".as_bytes(), 3, None, ValidationMode::Strict).unwrap();
+        assert_eq!(inventory_summary.elf_count, 2);
+        assert_eq!(top_elves[1].total_calories, 0);
+
+        let (top_elves, inventory_summary) = get_top_elves(
+            "100\n\n-5\n-1\n".as_bytes(),
+            3,
+            None,
+            ValidationMode::Lenient,
+        )
+        .unwrap();
+        assert_eq!(inventory_summary.elf_count, 2);
+        assert_eq!(inventory_summary.ignored.negative_values, 2);
+        assert_eq!(
+            top_elves[1],
+            ElfTotal {
+                elf_number: 2,
+                total_calories: -6
+            }
+        );
+    }
 }
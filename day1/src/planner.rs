@@ -0,0 +1,609 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use log::{debug, trace};
+
+use crate::inventory::ElfInventory;
+
+/// How much work each packing search can do before giving up, counted in carriers looked at.
+/// Packing snacks under a load is bin packing, so searching every way to do it can take far too long
+const SEARCH_WORK_LIMIT: usize = 20_000_000;
+
+/// One snack handed from one carrier to another. A carrier is an elf, or a group of elves when balancing groups.
+/// Carriers are numbered from 1, like elves
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SnackMove {
+    pub snack_calories: i64,
+    /// The elf the snack started with
+    pub from_elf: usize,
+    pub from_carrier: usize,
+    pub to_carrier: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LoadPlan {
+    pub carrier_count: usize,
+    /// The heaviest load, and which carrier had it. The first carrier wins a tie.
+    /// Loads are 128 bits, as with negative snacks a load can overflow 64 bits even when the total doesn't
+    pub max_load_before: (usize, i128),
+    pub max_load_after: (usize, i128),
+    /// No plan can do better than this: the average load, rounded up, or the largest snack,
+    /// as a snack can't be split. Only the average counts if there are negative snacks
+    pub lower_bound: i128,
+    /// Whether the search proved no plan has a lighter heaviest load. It can't when a search hit
+    /// SEARCH_WORK_LIMIT, or when there are negative snacks, as then it can't rule a load out
+    pub is_best_possible: bool,
+    /// In the order the snacks appear in the inventory. Snacks are put back with the carrier they started with
+    /// wherever the heaviest load allows, so there are few moves, but they aren't proven to be the fewest possible
+    pub moves: Vec<SnackMove>,
+}
+
+struct Snack {
+    calories: i64,
+    elf_number: usize,
+    original_carrier: usize,
+}
+
+/// What pack_snacks found
+enum Packing {
+    /// The carrier for each snack
+    Packed(Vec<usize>),
+    /// The snacks can't be packed under the load
+    Impossible,
+    /// The search hit SEARCH_WORK_LIMIT before it could tell
+    GaveUp,
+}
+
+/// How far pack_snacks has got with placing one snack
+#[derive(Default)]
+struct Placement {
+    /// 0 is the carrier the snack started with, 1 is the carrier with the most room, and 2 on is every carrier in order
+    next_choice: usize,
+    /// The carrier the snack is on now
+    carrier: Option<usize>,
+    /// The loads of the carriers already tried, before the snack went on them
+    tried_loads: HashSet<i128>,
+}
+
+/// Plan which snacks to hand around so the heaviest load is as light as it can be.
+///
+/// With a group count, the elves are split into that many groups of consecutive elves, as evenly as possible,
+/// and a group's load is all its elves' snacks together. Otherwise every elf carries their own load.
+///
+/// Binary searches on the heaviest load, between the lower bound and the heaviest load now,
+/// asking pack_snacks whether the snacks fit under each load. The way they're packed now always fits
+/// under the heaviest load now, so there's always a plan. When a search gives up, that load is treated
+/// as not fitting, so the plan is still the best one found, but it might not be the best possible.
+///
+/// pack_snacks is only after a packing that fits, and a search that backs up a lot can leave many snacks
+/// away from where they started. So once the load is settled, keep_snacks_with_carriers moves back
+/// every snack it can without going over that load
+pub fn plan_load_balance(elf_inventories: &[ElfInventory], group_count: Option<usize>) -> LoadPlan {
+    let elf_count = elf_inventories.len();
+    // Every group needs at least one elf
+    let carrier_count = group_count.unwrap_or(elf_count).clamp(1, elf_count.max(1));
+
+    let mut snacks: Vec<Snack> = Vec::new();
+    for (elf_index, elf_inventory) in elf_inventories.iter().enumerate() {
+        // Consecutive elves share a group, and group sizes differ by one at most
+        let carrier = elf_index * carrier_count / elf_count;
+        for &calories in &elf_inventory.snack_calories {
+            snacks.push(Snack {
+                calories,
+                elf_number: elf_inventory.elf_number,
+                original_carrier: carrier,
+            });
+        }
+    }
+
+    let mut snack_carriers: Vec<usize> =
+        snacks.iter().map(|snack| snack.original_carrier).collect();
+    let max_load_before = get_max_load(&get_carrier_loads(&snacks, &snack_carriers, carrier_count));
+    let lower_bound = get_lower_bound(&snacks, carrier_count);
+    // With negative snacks a carrier can go over the load and come back under it later, which pack_snacks
+    // doesn't try, so it can't rule a load out
+    let can_rule_out_loads = snacks.iter().all(|snack| snack.calories >= 0);
+    debug!(
+        "Balancing {} snacks across {} carriers. Heaviest load {:?}, lower bound {}",
+        snacks.len(),
+        carrier_count,
+        max_load_before,
+        lower_bound
+    );
+
+    // Every load from lowest_unknown_load down to the lower bound might still fit.
+    // Everything from lowest_packed_load up does
+    let mut lowest_packed_load = max_load_before.1;
+    let mut lowest_unknown_load = lower_bound;
+    let mut highest_ruled_out_load: Option<i128> = None;
+    while lowest_unknown_load < lowest_packed_load {
+        let max_load = (lowest_unknown_load + lowest_packed_load).div_euclid(2);
+        match pack_snacks(&snacks, carrier_count, max_load) {
+            Packing::Packed(packed_carriers) => {
+                snack_carriers = packed_carriers;
+                lowest_packed_load =
+                    get_max_load(&get_carrier_loads(&snacks, &snack_carriers, carrier_count)).1;
+                debug!("The snacks fit under {} calories", lowest_packed_load);
+            }
+            Packing::Impossible if can_rule_out_loads => {
+                highest_ruled_out_load = Some(max_load);
+                lowest_unknown_load = max_load + 1;
+                debug!("The snacks don't fit under {} calories", max_load);
+            }
+            Packing::Impossible | Packing::GaveUp => {
+                lowest_unknown_load = max_load + 1;
+                debug!(
+                    "Couldn't tell if the snacks fit under {} calories",
+                    max_load
+                );
+            }
+        }
+    }
+    let is_best_possible =
+        lowest_packed_load == lower_bound || highest_ruled_out_load == Some(lowest_packed_load - 1);
+    keep_snacks_with_carriers(
+        &snacks,
+        &mut snack_carriers,
+        carrier_count,
+        lowest_packed_load,
+    );
+
+    let moves = snacks
+        .iter()
+        .zip(&snack_carriers)
+        .filter(|(snack, &carrier)| carrier != snack.original_carrier)
+        .map(|(snack, &carrier)| SnackMove {
+            snack_calories: snack.calories,
+            from_elf: snack.elf_number,
+            from_carrier: snack.original_carrier + 1,
+            to_carrier: carrier + 1,
+        })
+        .collect();
+
+    let (heaviest_carrier_before, max_calories_before) = max_load_before;
+    let (heaviest_carrier_after, max_calories_after) =
+        get_max_load(&get_carrier_loads(&snacks, &snack_carriers, carrier_count));
+    LoadPlan {
+        carrier_count,
+        max_load_before: (heaviest_carrier_before + 1, max_calories_before),
+        max_load_after: (heaviest_carrier_after + 1, max_calories_after),
+        lower_bound,
+        is_best_possible,
+        moves,
+    }
+}
+
+/// Added up in 128 bits. The total always fits in 64 bits, but with negative snacks a partial sum might not
+fn get_carrier_loads(
+    snacks: &[Snack],
+    snack_carriers: &[usize],
+    carrier_count: usize,
+) -> Vec<i128> {
+    let mut carrier_loads = vec![0; carrier_count];
+    for (snack, &carrier) in snacks.iter().zip(snack_carriers) {
+        carrier_loads[carrier] += snack.calories as i128;
+    }
+    carrier_loads
+}
+
+/// (Carrier index, Load) of the heaviest carrier. The first carrier wins a tie
+fn get_max_load(carrier_loads: &[i128]) -> (usize, i128) {
+    carrier_loads
+        .iter()
+        .copied()
+        .enumerate()
+        .fold((0, i128::MIN), |heaviest, (carrier, load)| {
+            if load > heaviest.1 {
+                (carrier, load)
+            } else {
+                heaviest
+            }
+        })
+}
+
+fn get_lower_bound(snacks: &[Snack], carrier_count: usize) -> i128 {
+    let total_calories: i128 = snacks.iter().map(|snack| snack.calories as i128).sum();
+    let average_load = total_calories.div_euclid(carrier_count as i128)
+        + i128::from(total_calories.rem_euclid(carrier_count as i128) > 0);
+
+    if snacks.iter().any(|snack| snack.calories < 0) {
+        return average_load;
+    }
+    let largest_snack = snacks
+        .iter()
+        .map(|snack| snack.calories as i128)
+        .max()
+        .unwrap_or(0);
+    average_load.max(largest_snack)
+}
+
+/// Look for a way to pack the snacks so no carrier's load is over max_load.
+///
+/// Places the heaviest snacks first, as they're the hardest to fit. Each snack tries the carrier it started with
+/// first, so snacks only move when they have to, then the carrier with the most room once its own snacks still
+/// to come are counted, so those are less likely to be pushed off it later. If the lightest carrier has no room
+/// then nobody does, so it backs up and tries the snack before somewhere else,
+/// going through every carrier with room. Carriers with the same load are as good as each other,
+/// so only one of them gets tried for each snack
+fn pack_snacks(snacks: &[Snack], carrier_count: usize, max_load: i128) -> Packing {
+    if snacks.is_empty() {
+        return Packing::Packed(Vec::new());
+    }
+    let mut snack_order: Vec<usize> = (0..snacks.len()).collect();
+    snack_order.sort_by_key(|&snack_index| Reverse(snacks[snack_index].calories));
+
+    let mut carrier_loads: Vec<i128> = vec![0; carrier_count];
+    // What each carrier started with that hasn't been placed yet
+    let mut loads_to_come: Vec<i128> = vec![0; carrier_count];
+    for snack in snacks {
+        loads_to_come[snack.original_carrier] += snack.calories as i128;
+    }
+    // One for each snack placed so far, and the one being placed. A stack rather than recursion,
+    // as there's a level for every snack
+    let mut placements: Vec<Placement> = vec![Placement::default()];
+    let mut work_left = SEARCH_WORK_LIMIT;
+
+    loop {
+        let snack = &snacks[snack_order[placements.len() - 1]];
+        let placement = placements
+            .last_mut()
+            .expect("There is always a snack being placed");
+        if let Some(carrier) = placement.carrier.take() {
+            carrier_loads[carrier] -= snack.calories as i128;
+            loads_to_come[snack.original_carrier] += snack.calories as i128;
+        }
+
+        match get_next_carrier(
+            placement,
+            snack,
+            &carrier_loads,
+            &loads_to_come,
+            max_load,
+            &mut work_left,
+        ) {
+            Some(carrier) => {
+                carrier_loads[carrier] += snack.calories as i128;
+                loads_to_come[snack.original_carrier] -= snack.calories as i128;
+                placement.carrier = Some(carrier);
+                if placements.len() == snacks.len() {
+                    break;
+                }
+                placements.push(Placement::default());
+            }
+            None if work_left == 0 => return Packing::GaveUp,
+            None => {
+                placements.pop();
+                if placements.is_empty() {
+                    return Packing::Impossible;
+                }
+                trace!(
+                    "Backing up to snack {} of {}",
+                    placements.len(),
+                    snacks.len()
+                );
+            }
+        }
+    }
+
+    let mut snack_carriers = vec![0; snacks.len()];
+    for (&snack_index, placement) in snack_order.iter().zip(&placements) {
+        snack_carriers[snack_index] = placement.carrier.expect("Every snack has been placed");
+    }
+    Packing::Packed(snack_carriers)
+}
+
+/// The next carrier to try the snack on, or None once there's nowhere left worth trying, or no work left
+fn get_next_carrier(
+    placement: &mut Placement,
+    snack: &Snack,
+    carrier_loads: &[i128],
+    loads_to_come: &[i128],
+    max_load: i128,
+    work_left: &mut usize,
+) -> Option<usize> {
+    let calories = snack.calories as i128;
+    loop {
+        let carrier = match placement.next_choice {
+            0 => snack.original_carrier,
+            1 => {
+                *work_left = work_left.saturating_sub(carrier_loads.len());
+                let lightest_carrier = (0..carrier_loads.len())
+                    .min_by_key(|&carrier| carrier_loads[carrier])
+                    .expect("There is always at least one carrier");
+                if carrier_loads[lightest_carrier] + calories > max_load {
+                    return None;
+                }
+                // Of the carriers with room now, the one with the most room once its own snacks still to come
+                // are on it. That leaves the fewest of them needing to move somewhere else later
+                (0..carrier_loads.len())
+                    .filter(|&carrier| carrier_loads[carrier] + calories <= max_load)
+                    .min_by_key(|&carrier| carrier_loads[carrier] + loads_to_come[carrier])
+                    .expect("The lightest carrier has room")
+            }
+            choice => {
+                let carrier = choice - 2;
+                if carrier >= carrier_loads.len() {
+                    return None;
+                }
+                carrier
+            }
+        };
+        placement.next_choice += 1;
+
+        if *work_left == 0 {
+            return None;
+        }
+        *work_left -= 1;
+        let load = carrier_loads[carrier];
+        if load + calories <= max_load && placement.tried_loads.insert(load) {
+            return Some(carrier);
+        }
+    }
+}
+
+fn get_move_count(snacks: &[Snack], snack_carriers: &[usize]) -> usize {
+    snacks
+        .iter()
+        .zip(snack_carriers)
+        .filter(|(snack, &carrier)| carrier != snack.original_carrier)
+        .count()
+}
+
+/// Cut down the moves in a packing, without any carrier's load going over max_load.
+///
+/// Each moved snack goes back to the carrier it started with if that carrier has room for it. If not,
+/// a snack on that carrier that doesn't belong there either makes way for it, going to any carrier with room.
+/// Either way at least one snack ends up back where it started, so it goes round until nothing changes.
+/// This only looks at one or two snacks at a time, so it can miss moves that need several snacks shuffled round
+fn keep_snacks_with_carriers(
+    snacks: &[Snack],
+    snack_carriers: &mut [usize],
+    carrier_count: usize,
+    max_load: i128,
+) {
+    let mut carrier_loads = get_carrier_loads(snacks, snack_carriers, carrier_count);
+    let fits = |load: i128| load <= max_load;
+
+    let mut any_kept = true;
+    while any_kept {
+        any_kept = false;
+        for snack_index in 0..snacks.len() {
+            let carrier = snack_carriers[snack_index];
+            let original_carrier = snacks[snack_index].original_carrier;
+            if carrier == original_carrier {
+                continue;
+            }
+            let calories = snacks[snack_index].calories as i128;
+
+            // A negative snack makes the carrier it leaves heavier, so both loads need checking
+            if fits(carrier_loads[original_carrier] + calories)
+                && fits(carrier_loads[carrier] - calories)
+            {
+                carrier_loads[original_carrier] += calories;
+                carrier_loads[carrier] -= calories;
+                snack_carriers[snack_index] = original_carrier;
+                any_kept = true;
+                continue;
+            }
+
+            // Otherwise make room by moving a snack that doesn't belong on the original carrier either.
+            // It tries its own carrier first, as then it's back where it started too, then any carrier with room
+            let load_without_snack = |load_carrier: usize| {
+                carrier_loads[load_carrier] - if load_carrier == carrier { calories } else { 0 }
+            };
+            let swap = (0..snacks.len())
+                .filter(|&other_index| {
+                    snack_carriers[other_index] == original_carrier
+                        && snacks[other_index].original_carrier != original_carrier
+                })
+                .find_map(|other_index| {
+                    let other_calories = snacks[other_index].calories as i128;
+                    if !fits(carrier_loads[original_carrier] + calories - other_calories) {
+                        return None;
+                    }
+                    std::iter::once(snacks[other_index].original_carrier)
+                        .chain(0..carrier_count)
+                        .filter(|&new_carrier| new_carrier != original_carrier)
+                        .find(|&new_carrier| {
+                            fits(load_without_snack(new_carrier) + other_calories)
+                                && (new_carrier == carrier || fits(load_without_snack(carrier)))
+                        })
+                        .map(|new_carrier| (other_index, new_carrier))
+                });
+            if let Some((other_index, new_carrier)) = swap {
+                let other_calories = snacks[other_index].calories as i128;
+                carrier_loads[original_carrier] += calories - other_calories;
+                carrier_loads[carrier] -= calories;
+                carrier_loads[new_carrier] += other_calories;
+                snack_carriers[snack_index] = original_carrier;
+                snack_carriers[other_index] = new_carrier;
+                any_kept = true;
+            }
+        }
+        trace!(
+            "{} snacks away from where they started",
+            get_move_count(snacks, snack_carriers)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elf_inventories(snack_lists: &[&[i64]]) -> Vec<ElfInventory> {
+        snack_lists
+            .iter()
+            .enumerate()
+            .map(|(elf_index, snack_calories)| ElfInventory {
+                elf_number: elf_index + 1,
+                elf_name: None,
+                snack_calories: snack_calories.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Replay the moves on the starting loads, to check they give the load the plan says they do
+    fn get_loads_after_moves(
+        elf_inventories: &[ElfInventory],
+        group_count: usize,
+        load_plan: &LoadPlan,
+    ) -> Vec<i128> {
+        let mut carrier_loads = vec![0; group_count];
+        for (elf_index, elf_inventory) in elf_inventories.iter().enumerate() {
+            carrier_loads[elf_index * group_count / elf_inventories.len()] += elf_inventory
+                .snack_calories
+                .iter()
+                .map(|&calories| calories as i128)
+                .sum::<i128>();
+        }
+        for snack_move in &load_plan.moves {
+            carrier_loads[snack_move.from_carrier - 1] -= snack_move.snack_calories as i128;
+            carrier_loads[snack_move.to_carrier - 1] += snack_move.snack_calories as i128;
+        }
+        carrier_loads
+    }
+
+    #[test]
+    fn balanced_elves_need_no_moves() {
+        let elf_inventories = elf_inventories(&[&[10], &[1]]);
+        let load_plan = plan_load_balance(&elf_inventories, None);
+
+        // Handing over the only snack would just move the load
+        assert_eq!(load_plan.max_load_before, (1, 10));
+        assert_eq!(load_plan.max_load_after, (1, 10));
+        assert_eq!(load_plan.lower_bound, 10);
+        assert!(load_plan.is_best_possible);
+        assert_eq!(load_plan.moves, vec![]);
+    }
+
+    #[test]
+    fn snacks_go_to_an_empty_elf() {
+        let elf_inventories = elf_inventories(&[&[5, 5], &[]]);
+        let load_plan = plan_load_balance(&elf_inventories, None);
+
+        assert_eq!(load_plan.max_load_after.1, 5);
+        assert_eq!(
+            load_plan.moves,
+            vec![SnackMove {
+                snack_calories: 5,
+                from_elf: 1,
+                from_carrier: 1,
+                to_carrier: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn swaps_find_what_moves_cant() {
+        // Any single move makes things worse. Swapping the 6 and the 4 evens them out
+        let elf_inventories = elf_inventories(&[&[6, 6], &[4, 4]]);
+        let load_plan = plan_load_balance(&elf_inventories, None);
+
+        assert_eq!(load_plan.max_load_after.1, 10);
+        assert_eq!(load_plan.moves.len(), 2);
+        assert_eq!(
+            get_loads_after_moves(&elf_inventories, 2, &load_plan),
+            vec![10, 10]
+        );
+    }
+
+    #[test]
+    fn example_elves_and_groups() {
+        let elf_inventories = elf_inventories(&[
+            &[1000, 2000, 3000],
+            &[4000],
+            &[5000, 6000],
+            &[7000, 8000, 9000],
+            &[10000],
+        ]);
+
+        let load_plan = plan_load_balance(&elf_inventories, None);
+        assert_eq!(load_plan.max_load_before, (4, 24000));
+        assert_eq!(load_plan.lower_bound, 11000);
+        // Every elf can carry exactly 11000, like 10000 and 1000, or 9000 and 2000
+        assert_eq!(load_plan.max_load_after.1, 11000);
+        assert!(load_plan.is_best_possible);
+        let carrier_loads = get_loads_after_moves(&elf_inventories, 5, &load_plan);
+        assert_eq!(
+            carrier_loads.iter().max(),
+            Some(&load_plan.max_load_after.1)
+        );
+        assert_eq!(carrier_loads.iter().sum::<i128>(), 55000);
+
+        // Elves 1 to 3 are the first group, carrying 21000, and elves 4 and 5 are the second, carrying 34000
+        let load_plan = plan_load_balance(&elf_inventories, Some(2));
+        assert_eq!(load_plan.max_load_before, (2, 34000));
+        assert_eq!(load_plan.lower_bound, 27500);
+        // Every snack is a whole thousand calories, so 27500 can't be reached
+        assert_eq!(load_plan.max_load_after.1, 28000);
+        assert!(load_plan.is_best_possible);
+        assert_eq!(
+            get_loads_after_moves(&elf_inventories, 2, &load_plan),
+            vec![28000, 27000]
+        );
+    }
+
+    #[test]
+    fn more_groups_than_elves() {
+        let elf_inventories = elf_inventories(&[&[3, 1]]);
+        let load_plan = plan_load_balance(&elf_inventories, Some(4));
+
+        assert_eq!(load_plan.carrier_count, 1);
+        assert_eq!(load_plan.max_load_after, (1, 4));
+        assert_eq!(plan_load_balance(&[], None).moves, vec![]);
+    }
+
+    #[test]
+    fn negative_snacks_stop_loads_being_ruled_out() {
+        // 4 is the best possible, as splitting up the 6 and the -2 leaves someone carrying 6,
+        // but the search can't rule out 3 without trying the 6 on its own first
+        let elf_inventories = elf_inventories(&[&[6, -2], &[]]);
+        let load_plan = plan_load_balance(&elf_inventories, None);
+
+        assert_eq!(load_plan.lower_bound, 2);
+        assert_eq!(load_plan.max_load_after, (1, 4));
+        assert!(!load_plan.is_best_possible);
+        assert_eq!(load_plan.moves, vec![]);
+    }
+
+    #[test]
+    fn loads_can_pass_the_64_bit_limit() {
+        // The total fits in 64 bits, but the first two snacks together don't
+        let elf_inventories = elf_inventories(&[&[i64::MAX, i64::MAX, -i64::MAX], &[]]);
+        let load_plan = plan_load_balance(&elf_inventories, None);
+
+        assert_eq!(load_plan.max_load_before, (1, i64::MAX as i128));
+        assert_eq!(load_plan.max_load_after.1, i64::MAX as i128);
+        assert_eq!(load_plan.moves, vec![]);
+    }
+
+    #[test]
+    fn moved_snacks_go_back_where_they_can() {
+        let snacks: Vec<Snack> = [(4, 0), (4, 1), (4, 2), (1, 2)]
+            .iter()
+            .map(|&(calories, original_carrier)| Snack {
+                calories,
+                elf_number: original_carrier + 1,
+                original_carrier,
+            })
+            .collect();
+
+        // Every snack moved along one. The 1 can simply go back, and the 4s have to swap their way home
+        let mut snack_carriers = vec![1, 2, 0, 0];
+        keep_snacks_with_carriers(&snacks, &mut snack_carriers, 3, 5);
+        assert_eq!(snack_carriers, vec![0, 1, 2, 2]);
+
+        // The second 3 had to move off carrier 0 to get under 3, so it has to stay moved
+        let snacks: Vec<Snack> = (0..2)
+            .map(|_| Snack {
+                calories: 3,
+                elf_number: 1,
+                original_carrier: 0,
+            })
+            .collect();
+        let mut snack_carriers = vec![0, 1];
+        keep_snacks_with_carriers(&snacks, &mut snack_carriers, 2, 3);
+        assert_eq!(snack_carriers, vec![0, 1]);
+    }
+}
@@ -0,0 +1,253 @@
+/// Which percentiles get reported, alongside the median
+pub const PERCENTILES: [u8; 6] = [10, 25, 50, 75, 90, 99];
+
+/// How many bins a histogram aims for. The bin width is rounded to a nice number, so it can end up a few more
+const TARGET_BIN_COUNT: i128 = 10;
+
+/// How many '#' the fullest histogram bin gets
+const HISTOGRAM_BAR_WIDTH: usize = 40;
+
+/// Summary statistics for a list of values, like the elves' totals or every snack's calories
+#[derive(Debug, PartialEq, Clone)]
+pub struct Statistics {
+    pub count: usize,
+    pub total: i128,
+    pub min: i64,
+    pub max: i64,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation, as every elf is counted rather than a sample of them
+    pub std_dev: f64,
+    /// (Percentile, Value) for each of PERCENTILES
+    pub percentiles: Vec<(u8, f64)>,
+}
+
+/// Work out the statistics for the values. None if there aren't any values
+pub fn get_statistics(values: &[i64]) -> Option<Statistics> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted_values = values.to_vec();
+    sorted_values.sort_unstable();
+
+    // Totalled in 128 bits, as a lot of 64 bit values can add up to more than 64 bits
+    let total: i128 = values.iter().map(|&value| value as i128).sum();
+    let mean = total as f64 / values.len() as f64;
+    let variance = values
+        .iter()
+        .map(|&value| (value as f64 - mean).powi(2))
+        .sum::<f64>()
+        / values.len() as f64;
+
+    Some(Statistics {
+        count: values.len(),
+        total,
+        min: sorted_values[0],
+        max: sorted_values[sorted_values.len() - 1],
+        mean,
+        median: get_percentile(&sorted_values, 50),
+        std_dev: variance.sqrt(),
+        percentiles: PERCENTILES
+            .iter()
+            .map(|&percentile| (percentile, get_percentile(&sorted_values, percentile)))
+            .collect(),
+    })
+}
+
+/// The value the given percent of the sorted values are below. Falls between two values,
+/// in which case it's interpolated linearly between them. The 50th percentile is the median
+fn get_percentile(sorted_values: &[i64], percentile: u8) -> f64 {
+    let rank = percentile as f64 / 100.0 * (sorted_values.len() - 1) as f64;
+    let lower_value = sorted_values[rank.floor() as usize] as f64;
+    let upper_value = sorted_values[rank.ceil() as usize] as f64;
+
+    lower_value + (upper_value - lower_value) * rank.fract()
+}
+
+/// A histogram bin covering lower to upper, inclusive at both ends
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HistogramBin {
+    pub lower: i64,
+    pub upper: i64,
+    pub count: usize,
+}
+
+/// Sort the values into equal width bins, from the bin holding the smallest value to the bin holding the largest.
+/// The width is 1, 2 or 5 times a power of ten, so the bins start at round numbers. Empty bins in between are kept
+pub fn get_histogram(values: &[i64]) -> Vec<HistogramBin> {
+    let (Some(&min_value), Some(&max_value)) = (values.iter().min(), values.iter().max()) else {
+        return Vec::new();
+    };
+
+    // Done in 128 bits, as the range between the smallest and largest 64 bit values doesn't fit in 64 bits
+    let bin_width = get_nice_bin_width((max_value as i128 - min_value as i128) / TARGET_BIN_COUNT);
+    let first_bin_lower = (min_value as i128).div_euclid(bin_width) * bin_width;
+    let bin_count = ((max_value as i128 - first_bin_lower) / bin_width + 1) as usize;
+
+    let mut bin_counts = vec![0; bin_count];
+    for &value in values {
+        bin_counts[((value as i128 - first_bin_lower) / bin_width) as usize] += 1;
+    }
+
+    let clamp_to_i64 = |bound: i128| bound.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+    bin_counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin_index, count)| {
+            let bin_lower = first_bin_lower + bin_index as i128 * bin_width;
+            HistogramBin {
+                lower: clamp_to_i64(bin_lower),
+                upper: clamp_to_i64(bin_lower + bin_width - 1),
+                count,
+            }
+        })
+        .collect()
+}
+
+/// The smallest of 1, 2, 5, 10, 20, 50, ... that's at least the given width
+fn get_nice_bin_width(min_width: i128) -> i128 {
+    let mut power_of_ten: i128 = 1;
+    loop {
+        for multiplier in [1, 2, 5] {
+            if multiplier * power_of_ten >= min_width {
+                return multiplier * power_of_ten;
+            }
+        }
+        power_of_ten *= 10;
+    }
+}
+
+/// One line per bin, like "4000 - 5999 |  2 ########". The fullest bin's bar is HISTOGRAM_BAR_WIDTH long,
+/// and any bin with something in it gets at least one '#'
+pub fn render_histogram(histogram_bins: &[HistogramBin]) -> Vec<String> {
+    let max_count = histogram_bins
+        .iter()
+        .map(|histogram_bin| histogram_bin.count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let bound_width = histogram_bins
+        .iter()
+        .flat_map(|histogram_bin| [histogram_bin.lower, histogram_bin.upper])
+        .map(|bound| bound.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let count_width = max_count.to_string().len();
+
+    histogram_bins
+        .iter()
+        .map(|histogram_bin| {
+            let bar_length = (histogram_bin.count * HISTOGRAM_BAR_WIDTH).div_ceil(max_count);
+            format!(
+                "{:>bound_width$} - {:>bound_width$} | {:>count_width$} {}",
+                histogram_bin.lower,
+                histogram_bin.upper,
+                histogram_bin.count,
+                "#".repeat(bar_length)
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn example_elf_total_statistics() {
+        let statistics = get_statistics(&[6000, 4000, 11000, 24000, 10000]).unwrap();
+
+        assert_eq!(statistics.count, 5);
+        assert_eq!(statistics.total, 55000);
+        assert_eq!((statistics.min, statistics.max), (4000, 24000));
+        assert_close(statistics.mean, 11000.0);
+        assert_close(statistics.median, 10000.0);
+        assert_close(statistics.std_dev, 6985.70);
+
+        let percentile_values: Vec<f64> = statistics
+            .percentiles
+            .iter()
+            .map(|&(_, value)| value)
+            .collect();
+        for (actual, expected) in percentile_values
+            .into_iter()
+            .zip([4800.0, 6000.0, 10000.0, 11000.0, 18800.0, 23480.0])
+        {
+            assert_close(actual, expected);
+        }
+    }
+
+    #[test]
+    fn single_value_and_no_values() {
+        let statistics = get_statistics(&[7]).unwrap();
+        assert_close(statistics.median, 7.0);
+        assert_close(statistics.std_dev, 0.0);
+        assert!(statistics
+            .percentiles
+            .iter()
+            .all(|&(_, value)| value == 7.0));
+
+        assert_eq!(get_statistics(&[]), None);
+        assert_eq!(get_histogram(&[]), vec![]);
+    }
+
+    #[test]
+    fn histogram_uses_round_bins() {
+        let histogram_bins = get_histogram(&[6000, 4000, 11000, 24000, 10000]);
+
+        assert_eq!(histogram_bins.len(), 11);
+        assert_eq!(
+            histogram_bins[0],
+            HistogramBin {
+                lower: 4000,
+                upper: 5999,
+                count: 1
+            }
+        );
+        assert_eq!(histogram_bins[3].count, 2);
+        assert_eq!(histogram_bins[10].upper, 25999);
+        assert_eq!(
+            histogram_bins
+                .iter()
+                .map(|histogram_bin| histogram_bin.count)
+                .sum::<usize>(),
+            5
+        );
+
+        let histogram_lines = render_histogram(&histogram_bins);
+        assert_eq!(histogram_lines[0], " 4000 -  5999 | 1 ####################");
+        assert_eq!(histogram_lines[1], " 6000 -  7999 | 1 ####################");
+        assert_eq!(histogram_lines[2], " 8000 -  9999 | 0");
+        assert_eq!(
+            histogram_lines[3],
+            "10000 - 11999 | 2 ########################################"
+        );
+    }
+
+    #[test]
+    fn histogram_handles_extreme_values() {
+        let histogram_bins = get_histogram(&[i64::MIN, -1, i64::MAX]);
+
+        assert_eq!(histogram_bins.first().unwrap().lower, i64::MIN);
+        assert_eq!(histogram_bins.last().unwrap().upper, i64::MAX);
+        assert_eq!(
+            histogram_bins
+                .iter()
+                .map(|histogram_bin| histogram_bin.count)
+                .sum::<usize>(),
+            3
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// The total calories carried by one elf. Elves are numbered from 1, in the order they appear in the input
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfTotal {
+    pub elf_number: usize,
+    pub total_calories: i64,
+}
+
+/// Keeps the elves carrying the most calories out of all the elves pushed so far, up to a fixed count.
+///
+/// The kept elves sit in a min-heap, so the lightest of them is always on top. A new elf only has to beat that one
+/// to get in, which makes this O(elves * log N) with at most N elves held at once, instead of sorting every elf.
+/// Elves carrying the same calories are ranked by elf number, so the earlier elf wins the tie
+pub struct TopElves {
+    capacity: usize,
+    /// Reverse makes the max-heap a min-heap. The inner Reverse ranks lower elf numbers higher on ties
+    heap: BinaryHeap<Reverse<(i64, Reverse<usize>)>>,
+}
+
+impl TopElves {
+    pub fn new(capacity: usize) -> TopElves {
+        TopElves {
+            capacity,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    pub fn push(&mut self, elf_total: ElfTotal) {
+        let heap_entry = Reverse((elf_total.total_calories, Reverse(elf_total.elf_number)));
+
+        if self.heap.len() < self.capacity {
+            self.heap.push(heap_entry);
+        } else if let Some(lightest_entry) = self.heap.peek() {
+            // Smaller means heavier here, because of the Reverse
+            if heap_entry < *lightest_entry {
+                self.heap.pop();
+                self.heap.push(heap_entry);
+            }
+        }
+    }
+
+    /// The kept elves, heaviest first
+    pub fn into_sorted_vec(self) -> Vec<ElfTotal> {
+        // The heap's sorted order is lightest first once the Reverse is taken into account
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total_calories, Reverse(elf_number)))| ElfTotal {
+                elf_number,
+                total_calories,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elf_total(elf_number: usize, total_calories: i64) -> ElfTotal {
+        ElfTotal {
+            elf_number,
+            total_calories,
+        }
+    }
+
+    #[test]
+    fn keeps_heaviest_elves_in_order() {
+        let mut top_elves = TopElves::new(2);
+        for (elf_number, total_calories) in [(1, 300), (2, 900), (3, 100), (4, 700), (5, 800)] {
+            top_elves.push(elf_total(elf_number, total_calories));
+        }
+
+        assert_eq!(
+            top_elves.into_sorted_vec(),
+            vec![elf_total(2, 900), elf_total(5, 800)]
+        );
+    }
+
+    #[test]
+    fn ties_go_to_the_earlier_elf() {
+        let mut top_elves = TopElves::new(2);
+        for elf_number in 1..=4 {
+            top_elves.push(elf_total(elf_number, 500));
+        }
+
+        assert_eq!(
+            top_elves.into_sorted_vec(),
+            vec![elf_total(1, 500), elf_total(2, 500)]
+        );
+    }
+
+    #[test]
+    fn fewer_elves_than_capacity() {
+        let mut top_elves = TopElves::new(3);
+        top_elves.push(elf_total(1, 10));
+
+        assert_eq!(top_elves.into_sorted_vec(), vec![elf_total(1, 10)]);
+        assert_eq!(TopElves::new(0).into_sorted_vec(), vec![]);
+    }
+}
@@ -1,8 +1,6 @@
+use aoc_common::input;
 use log::{debug, trace, warn};
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::time::Instant;
 
 mod utils;
 use utils::{get_instruction_type, CpuState, InstructionType};
@@ -11,10 +9,28 @@ const INPUT_FILENAME: &str = "puzzle.txt";
 
 fn main() {
     env_logger::init();
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
     debug!("Starting Application!");
 
-    let input_file_lines =
-        BufReader::new(File::open(INPUT_FILENAME).expect("Could not open input file")).lines();
+    let (signal_strength_sum, crt_display_row) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the sum of these interesting signal strengths?\nAnswer: {}",
+        signal_strength_sum
+    );
+
+    println!("Part 2 | Pattern:\n{}", crt_display_row);
+}
+
+/// Run the program in the input file. Returns the sum of the interesting signal strengths,
+/// and the image drawn on the CRT
+fn solve(input_file_name: &str) -> (i32, String) {
+    let input_buffer = input::read_input(input_file_name);
 
     let mut cpu_state = utils::CpuState {
         register_x: 1,
@@ -27,44 +43,35 @@ fn main() {
     let mut crt_display_row: String = String::new();
 
     debug!("Initial CPU State {:?}", cpu_state);
-    for input_file_line in input_file_lines {
-        if let Ok(current_line) = input_file_line {
-            debug!("--");
-            debug!("Main | Current CPU State before executor {:?}", cpu_state);
-            executor(
-                &current_line,
-                &mut cpu_state,
-                &mut int_signal_strengths,
-                &mut crt_display_row,
-            );
-            debug!("Main | Current CPU State after executor {:?}", cpu_state);
-        }
+    for current_line in input_buffer.lines() {
+        debug!("--");
+        debug!("Main | Current CPU State before executor {:?}", cpu_state);
+        executor(
+            current_line,
+            &mut cpu_state,
+            &mut int_signal_strengths,
+            &mut crt_display_row,
+        );
+        debug!("Main | Current CPU State after executor {:?}", cpu_state);
     }
 
-    println!(
-        "Part 1 | What is the sum of these interesting signal strengths?\nAnswer: {}",
-        int_signal_strengths.iter().sum::<i32>()
-    );
-
-    println!("Part 2 | Pattern:\n{}", crt_display_row);
+    (int_signal_strengths.iter().sum::<i32>(), crt_display_row)
 }
 
 fn executor(
-    input_line: &String,
+    input_line: &str,
     cpu_state: &mut CpuState,
     int_signal_strengths: &mut Vec<i32>,
     crt_display_row: &mut String,
 ) {
     trace!("Executing line: {}", input_line);
 
-    let mut cycles_remaining = 0;
-
     let instruction_type = get_instruction_type(input_line);
 
-    match instruction_type {
-        InstructionType::Noop => cycles_remaining = 1,
-        InstructionType::Addx => cycles_remaining = 2,
-    }
+    let mut cycles_remaining = match instruction_type {
+        InstructionType::Noop => 1,
+        InstructionType::Addx => 2,
+    };
 
     while cycles_remaining > 0 {
         // Sometimes the moment when we need to get the signal strength is in the middle
@@ -93,7 +100,7 @@ fn executor(
             crt_display_row.push('.');
         }
         //Add newline after every 40th character
-        if (cpu_state.cycle_count >= 40) && (cpu_state.cycle_count % 40 == 0) {
+        if (cpu_state.cycle_count >= 40) && cpu_state.cycle_count.is_multiple_of(40) {
             crt_display_row.push('\n');
         }
         debug!("CRT Draw String:\n{}", crt_display_row);
@@ -108,12 +115,8 @@ fn executor(
         if (signal_strength - 20) % 40 == 0
 
         */
-        let mut cycle_count_interesting = false;
-        if (cpu_state.cycle_count >= 60) && ((cpu_state.cycle_count as i32 - 20) % 40) == 0 {
-            cycle_count_interesting = true;
-        } else if cpu_state.cycle_count == 20 {
-            cycle_count_interesting = true;
-        }
+        let cycle_count_interesting = (cpu_state.cycle_count == 20)
+            || ((cpu_state.cycle_count >= 60) && ((cpu_state.cycle_count as i32 - 20) % 40) == 0);
         // Record signal strength
         if cycle_count_interesting {
             let int_signal_strength = cpu_state.get_signal_strength();
@@ -134,8 +137,7 @@ fn executor(
                 if cycles_remaining == 1 {
                     let operand_value = input_line
                         .split_whitespace()
-                        .collect::<Vec<&str>>()
-                        .get(1)
+                        .nth(1)
                         .expect("Could not index new register value")
                         .parse::<i32>()
                         .expect("Could not parse new register value");
@@ -169,17 +171,76 @@ fn is_crt_drawing_sprite(cpu_state: &CpuState) -> bool {
 
     let mut sprite_middle_position = cpu_state.register_x;
 
-    if current_draw_position > 40 {
+    if current_draw_position >= 40 {
         sprite_middle_position += 40*(current_draw_position/40);
     }
 
     //Check if CRT is drawing one of the sprite pixels
-    if ((sprite_middle_position - 1) == current_draw_position)
+    ((sprite_middle_position - 1) == current_draw_position)
         || (sprite_middle_position == current_draw_position)
         || ((sprite_middle_position + 1) == current_draw_position)
-    {
-        return true;
+}
+
+/// Print the answers, the time taken to compute them, and the CRT image
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let solve_start = Instant::now();
+    let (signal_strength_sum, crt_display_row) = solve(input_file_name);
+    let solve_time = solve_start.elapsed();
+
+    println!("answer\tPart 1\t{}", signal_strength_sum);
+    println!("answer\tPart 2\tSee the CRT output");
+    // The signal strengths and the CRT pixels come from the same run of the program
+    println!("timing\tBoth parts\t{}", solve_time.as_nanos());
+
+    println!("grid\tCRT output");
+    for crt_line in crt_display_row.lines() {
+        println!("{}", crt_line);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        let (signal_strength_sum, crt_display_row) = solve("example.txt");
+
+        assert_eq!(signal_strength_sum, 13140);
+        assert_eq!(
+            crt_display_row,
+            "##..##..##..##..##..##..##..##..##..##..\n\
+             ###...###...###...###...###...###...###.\n\
+             ####....####....####....####....####....\n\
+             #####.....#####.....#####.....#####.....\n\
+             ######......######......######......####\n\
+             #######.......#######.......#######.....\n"
+        );
     }
 
-    return false;
+    #[test]
+    fn small_example_register_values() {
+        let mut cpu_state = CpuState {
+            register_x: 1,
+            cycle_count: 0,
+        };
+        let mut int_signal_strengths: Vec<i32> = Vec::new();
+        let mut crt_display_row = String::new();
+
+        // The three instruction program from the puzzle text
+        for input_line in ["noop", "addx 3", "addx -5"] {
+            executor(
+                input_line,
+                &mut cpu_state,
+                &mut int_signal_strengths,
+                &mut crt_display_row,
+            );
+        }
+
+        assert_eq!(cpu_state.cycle_count, 5);
+        assert_eq!(cpu_state.register_x, -1);
+        assert!(int_signal_strengths.is_empty());
+    }
 }
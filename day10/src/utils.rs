@@ -3,12 +3,12 @@ pub enum InstructionType {
     Addx,
 }
 
-pub fn get_instruction_type(input_line: &String) -> InstructionType {
+pub fn get_instruction_type(input_line: &str) -> InstructionType {
     if input_line.starts_with("noop") {
         return InstructionType::Noop;
     }
 
-    return InstructionType::Addx;
+    InstructionType::Addx
 }
 
 #[derive(Debug)]
@@ -19,6 +19,21 @@ pub struct CpuState {
 
 impl CpuState {
     pub fn get_signal_strength(&self) -> i32 {
-        return self.register_x * (self.cycle_count as i32);
+        self.register_x * (self.cycle_count as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_signal_strength_multiplies_register_by_cycle() {
+        // The 20th cycle from the larger example in the puzzle text
+        let cpu_state = CpuState {
+            register_x: 21,
+            cycle_count: 20,
+        };
+        assert_eq!(cpu_state.get_signal_strength(), 420);
     }
 }
@@ -0,0 +1,207 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::time::Instant;
+
+mod utils;
+use utils::{parse_monkeys, Monkey};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (part1_answer, part2_answer) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the level of monkey business after 20 rounds of stuff-slinging simian shenanigans?\nAnswer: {}",
+        part1_answer
+    );
+    println!(
+        "Part 2 | What is the level of monkey business after 10000 rounds, when worry levels are no longer divided by three?\nAnswer: {}",
+        part2_answer
+    );
+}
+
+/// How worry levels are kept in check after a monkey inspects an item
+#[derive(Debug, Clone, Copy)]
+enum WorryRelief {
+    /// Part 1 - Relief that the item wasn't damaged divides the worry level by three
+    DivideByThree,
+    /// Part 2 - There's no more relief, so worry levels grow without bound.
+    /// Every monkey only checks divisibility though. Taking the worry level modulo the product of all
+    /// the test divisors keeps it small, without changing the result of any monkey's test
+    ModuloCommonMultiple(u64),
+}
+
+/// Parse the monkeys in the input file, and return the level of monkey business for both parts
+fn solve(input_file_name: &str) -> (u64, u64) {
+    let input_string = input::read_input(input_file_name);
+    let monkeys = parse_monkeys(&input_string);
+    debug!("Parsed {} monkeys", monkeys.len());
+
+    (
+        get_part1_answer(monkeys.clone()),
+        get_part2_answer(monkeys).0,
+    )
+}
+
+fn get_part1_answer(mut monkeys: Vec<Monkey>) -> u64 {
+    simulate_rounds(&mut monkeys, 20, WorryRelief::DivideByThree);
+    get_monkey_business(&monkeys)
+}
+
+/// Also returns each monkey's inspection count, for the report
+fn get_part2_answer(mut monkeys: Vec<Monkey>) -> (u64, Vec<usize>) {
+    let common_multiple: u64 = monkeys.iter().map(|monkey| monkey.test_divisor).product();
+    debug!("Common multiple of all test divisors: {}", common_multiple);
+
+    simulate_rounds(
+        &mut monkeys,
+        10_000,
+        WorryRelief::ModuloCommonMultiple(common_multiple),
+    );
+
+    let inspection_counts = monkeys
+        .iter()
+        .map(|monkey| monkey.inspection_count)
+        .collect();
+    (get_monkey_business(&monkeys), inspection_counts)
+}
+
+fn simulate_rounds(monkeys: &mut [Monkey], round_count: usize, worry_relief: WorryRelief) {
+    for round_number in 1..=round_count {
+        // Monkeys take turns in order. Each one inspects and throws all the items it holds
+        for monkey_index in 0..monkeys.len() {
+            trace!("Monkey {}:", monkey_index);
+
+            while let Some(worry_level) = monkeys[monkey_index].items.pop_front() {
+                let current_monkey = &mut monkeys[monkey_index];
+                current_monkey.inspection_count += 1;
+
+                let mut new_worry_level = current_monkey.operation.apply(worry_level);
+                new_worry_level = match worry_relief {
+                    WorryRelief::DivideByThree => new_worry_level / 3,
+                    WorryRelief::ModuloCommonMultiple(common_multiple) => {
+                        new_worry_level % common_multiple
+                    }
+                };
+
+                let throw_target = current_monkey.get_throw_target(new_worry_level);
+                trace!(
+                    "Monkey inspects an item with a worry level of {}. Item with worry level {} is thrown to monkey {}",
+                    worry_level,
+                    new_worry_level,
+                    throw_target
+                );
+
+                monkeys[throw_target].items.push_back(new_worry_level);
+            }
+        }
+
+        trace!(
+            "After round {}, inspection counts are {:?}",
+            round_number,
+            monkeys
+                .iter()
+                .map(|monkey| monkey.inspection_count)
+                .collect::<Vec<usize>>()
+        );
+    }
+}
+
+/// Multiply the inspection counts of the two most active monkeys
+fn get_monkey_business(monkeys: &[Monkey]) -> u64 {
+    let mut inspection_counts: Vec<u64> = monkeys
+        .iter()
+        .map(|monkey| monkey.inspection_count as u64)
+        .collect();
+    inspection_counts.sort_by(|a, b| b.cmp(a));
+
+    debug!(
+        "Inspection counts, most active first: {:?}",
+        inspection_counts
+    );
+
+    inspection_counts.iter().take(2).product()
+}
+
+/// Print the answers, the time taken to compute each of them, and each monkey's part 2 inspection count
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let input_string = input::read_input(input_file_name);
+    let monkeys = parse_monkeys(&input_string);
+
+    let part1_start = Instant::now();
+    let part1_answer = get_part1_answer(monkeys.clone());
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let (part2_answer, inspection_counts) = get_part2_answer(monkeys);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", part1_answer);
+    println!("answer\tPart 2\t{}", part2_answer);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    println!("bars\tItems inspected by each monkey in 10000 rounds");
+    for (monkey_index, inspection_count) in inspection_counts.iter().enumerate() {
+        println!("0\tMonkey {}\t{}", monkey_index, inspection_count);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_inspection_counts(monkeys: &[Monkey]) -> Vec<usize> {
+        monkeys
+            .iter()
+            .map(|monkey| monkey.inspection_count)
+            .collect()
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (10605, 2713310158));
+    }
+
+    #[test]
+    fn example_items_after_first_round() {
+        let mut monkeys = parse_monkeys(&input::read_input("example.txt"));
+        simulate_rounds(&mut monkeys, 1, WorryRelief::DivideByThree);
+
+        assert_eq!(monkeys[0].items, [20, 23, 27, 26]);
+        assert_eq!(monkeys[1].items, [2080, 25, 167, 207, 401, 1046]);
+        assert!(monkeys[2].items.is_empty());
+        assert!(monkeys[3].items.is_empty());
+    }
+
+    #[test]
+    fn example_inspection_counts() {
+        let monkeys = parse_monkeys(&input::read_input("example.txt"));
+
+        let mut part1_monkeys = monkeys.clone();
+        simulate_rounds(&mut part1_monkeys, 20, WorryRelief::DivideByThree);
+        assert_eq!(get_inspection_counts(&part1_monkeys), vec![101, 95, 7, 105]);
+
+        let mut part2_monkeys = monkeys;
+        simulate_rounds(
+            &mut part2_monkeys,
+            10_000,
+            WorryRelief::ModuloCommonMultiple(23 * 19 * 13 * 17),
+        );
+        assert_eq!(
+            get_inspection_counts(&part2_monkeys),
+            vec![52166, 47830, 1938, 52013]
+        );
+    }
+}
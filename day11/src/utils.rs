@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use log::trace;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operand {
+    Old,
+    Value(u64),
+}
+
+impl Operand {
+    fn get_value(&self, old_worry_level: u64) -> u64 {
+        match self {
+            Self::Old => old_worry_level,
+            Self::Value(value) => *value,
+        }
+    }
+}
+
+/// The right hand side of "new = old <operator> <operand>"
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operation {
+    Add(Operand),
+    Multiply(Operand),
+}
+
+impl Operation {
+    pub fn apply(&self, old_worry_level: u64) -> u64 {
+        match self {
+            Self::Add(operand) => old_worry_level + operand.get_value(old_worry_level),
+            Self::Multiply(operand) => old_worry_level * operand.get_value(old_worry_level),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Monkey {
+    /// Worry levels of the items held, in the order they will be inspected
+    pub items: VecDeque<u64>,
+    pub operation: Operation,
+    pub test_divisor: u64,
+    pub throw_to_if_true: usize,
+    pub throw_to_if_false: usize,
+    pub inspection_count: usize,
+}
+
+impl Monkey {
+    /// Which monkey does an item with this worry level get thrown to?
+    pub fn get_throw_target(&self, worry_level: u64) -> usize {
+        if worry_level.is_multiple_of(self.test_divisor) {
+            return self.throw_to_if_true;
+        }
+
+        self.throw_to_if_false
+    }
+}
+
+/// Parse every monkey definition in the input. Definitions are separated by blank lines, and are in order
+/// Example:
+/// Monkey 0:
+///   Starting items: 79, 98
+///   Operation: new = old * 19
+///   Test: divisible by 23
+///     If true: throw to monkey 2
+///     If false: throw to monkey 3
+pub fn parse_monkeys(input_string: &str) -> Vec<Monkey> {
+    let mut monkeys: Vec<Monkey> = Vec::new();
+    let mut definition_lines: Vec<&str> = Vec::new();
+
+    // Chain an empty line so the last definition is parsed even when there's no trailing blank line
+    for input_line in input_string.lines().chain(std::iter::once("")) {
+        let input_line = input_line.trim();
+
+        if !input_line.is_empty() {
+            definition_lines.push(input_line);
+            continue;
+        }
+
+        if definition_lines.is_empty() {
+            continue;
+        }
+
+        trace!("Parsing monkey definition {:?}", definition_lines);
+        monkeys.push(parse_monkey(&definition_lines));
+        definition_lines.clear();
+    }
+
+    monkeys
+}
+
+fn parse_monkey(definition_lines: &[&str]) -> Monkey {
+    let items: VecDeque<u64> = get_line_value(definition_lines, "Starting items:")
+        .split(',')
+        .map(|item| {
+            item.trim()
+                .parse::<u64>()
+                .expect("Could not parse item worry level")
+        })
+        .collect();
+
+    let operation = parse_operation(get_line_value(definition_lines, "Operation: new = old"));
+
+    // "Test: divisible by 23", "If true: throw to monkey 2" and "If false: throw to monkey 3" all end with the number we want
+    let test_divisor = get_last_number(get_line_value(definition_lines, "Test:"));
+    let throw_to_if_true = get_last_number(get_line_value(definition_lines, "If true:")) as usize;
+    let throw_to_if_false = get_last_number(get_line_value(definition_lines, "If false:")) as usize;
+
+    Monkey {
+        items,
+        operation,
+        test_divisor,
+        throw_to_if_true,
+        throw_to_if_false,
+        inspection_count: 0,
+    }
+}
+
+/// Find the line starting with the prefix, and return what comes after the prefix
+fn get_line_value<'a>(definition_lines: &[&'a str], line_prefix: &str) -> &'a str {
+    definition_lines
+        .iter()
+        .find_map(|definition_line| definition_line.strip_prefix(line_prefix))
+        .unwrap_or_else(|| {
+            panic!(
+                "Monkey definition has no line starting with {}",
+                line_prefix
+            )
+        })
+}
+
+fn get_last_number(line_value: &str) -> u64 {
+    line_value
+        .split_whitespace()
+        .last()
+        .expect("Line has no number")
+        .parse::<u64>()
+        .expect("Could not parse number at the end of the line")
+}
+
+/// Take what comes after "new = old", aka "* 19", "+ 6" or "* old"
+fn parse_operation(operation_str: &str) -> Operation {
+    let (operator, operand_str) = operation_str
+        .trim()
+        .split_once(' ')
+        .expect("Could not split operation into operator and operand");
+
+    let operand = match operand_str.trim() {
+        "old" => Operand::Old,
+        value_str => Operand::Value(
+            value_str
+                .parse::<u64>()
+                .expect("Could not parse operation operand"),
+        ),
+    };
+
+    match operator {
+        "+" => Operation::Add(operand),
+        "*" => Operation::Multiply(operand),
+        strange_value => panic!("Could not match operator! Found: {}", strange_value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_monkeys_reads_example_definitions() {
+        let monkeys = parse_monkeys(&std::fs::read_to_string("example.txt").unwrap());
+
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(
+            monkeys[0],
+            Monkey {
+                items: VecDeque::from([79, 98]),
+                operation: Operation::Multiply(Operand::Value(19)),
+                test_divisor: 23,
+                throw_to_if_true: 2,
+                throw_to_if_false: 3,
+                inspection_count: 0,
+            }
+        );
+        assert_eq!(monkeys[1].operation, Operation::Add(Operand::Value(6)));
+        assert_eq!(monkeys[2].operation, Operation::Multiply(Operand::Old));
+        assert_eq!(monkeys[3].items, VecDeque::from([74]));
+    }
+
+    #[test]
+    fn operation_apply() {
+        assert_eq!(Operation::Multiply(Operand::Value(19)).apply(79), 1501);
+        assert_eq!(Operation::Add(Operand::Value(6)).apply(54), 60);
+        assert_eq!(Operation::Multiply(Operand::Old).apply(79), 6241);
+        assert_eq!(Operation::Add(Operand::Old).apply(3), 6);
+    }
+}
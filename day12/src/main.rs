@@ -0,0 +1,291 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::time::Instant;
+
+mod utils;
+use utils::bfs;
+
+const INPUT_FILENAME: &str = "example.txt";
+
+/// (row_index, column_index) of a square on the heightmap
+type Position = (usize, usize);
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (steps_from_start, steps_from_any_a) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the fewest steps required to move from your current position to the location that should get the best signal?\nAnswer: {}",
+        steps_from_start
+    );
+    println!(
+        "Part 2 | What is the fewest steps required to move starting from any square with elevation a to the location that should get the best signal?\nAnswer: {}",
+        steps_from_any_a
+    );
+
+    // Draw both paths on the map, the way the puzzle text does
+    if std::env::args().any(|arg| arg == "--path") {
+        let heightmap = Heightmap::new(&input::read_input(INPUT_FILENAME));
+
+        println!("\nPath from S:");
+        for map_line in get_path_map(&heightmap, &heightmap.get_path_from_start()) {
+            println!("{}", map_line);
+        }
+
+        println!("\nPath from the best 'a' square:");
+        for map_line in get_path_map(&heightmap, &heightmap.get_path_from_any_a()) {
+            println!("{}", map_line);
+        }
+    }
+}
+
+struct Heightmap {
+    /// Elevation of every square, 0 for 'a' through 25 for 'z'
+    height_matrix: Vec<Vec<usize>>,
+    start_position: Position,
+    end_position: Position,
+}
+
+impl Heightmap {
+    fn new(input_string: &str) -> Heightmap {
+        let mut height_matrix: Vec<Vec<usize>> = Vec::new();
+        let (start_position, end_position) = process_input(&mut height_matrix, input_string);
+        trace!("Height matrix: {:?}", height_matrix);
+
+        Heightmap {
+            height_matrix,
+            start_position,
+            end_position,
+        }
+    }
+
+    /// Squares we can step to from this one. The destination can be at most one higher,
+    /// but can be as much lower as it likes
+    fn get_neighbours(&self, position: &Position) -> Vec<Position> {
+        let (row_index, column_index) = *position;
+        let current_height = self.height_matrix[row_index][column_index];
+
+        let mut neighbours: Vec<Position> = Vec::with_capacity(4);
+        if row_index > 0 {
+            neighbours.push((row_index - 1, column_index));
+        }
+        if row_index + 1 < self.height_matrix.len() {
+            neighbours.push((row_index + 1, column_index));
+        }
+        if column_index > 0 {
+            neighbours.push((row_index, column_index - 1));
+        }
+        if column_index + 1 < self.height_matrix[row_index].len() {
+            neighbours.push((row_index, column_index + 1));
+        }
+
+        neighbours.retain(|&(neighbour_row, neighbour_column)| {
+            self.height_matrix[neighbour_row][neighbour_column] <= current_height + 1
+        });
+
+        neighbours
+    }
+
+    /// Part 1 - Shortest path from S to E
+    fn get_path_from_start(&self) -> Vec<Position> {
+        bfs(
+            [self.start_position],
+            |position| *position == self.end_position,
+            |position| self.get_neighbours(position),
+        )
+        .expect("No path from S to E!")
+    }
+
+    /// Part 2 - Shortest path from any lowest square to E.
+    /// Rather than searching once per 'a' square, start a single search from all of them at once
+    fn get_path_from_any_a(&self) -> Vec<Position> {
+        let lowest_positions = self
+            .height_matrix
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, height_row)| {
+                height_row
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &height)| height == 0)
+                    .map(move |(column_index, _)| (row_index, column_index))
+            });
+
+        bfs(
+            lowest_positions,
+            |position| *position == self.end_position,
+            |position| self.get_neighbours(position),
+        )
+        .expect("No path from any 'a' square to E!")
+    }
+}
+
+/// Find the fewest steps from S to E, and from any 'a' square to E
+fn solve(input_file_name: &str) -> (usize, usize) {
+    let heightmap = Heightmap::new(&input::read_input(input_file_name));
+    debug!(
+        "Start: {:?}, End: {:?}",
+        heightmap.start_position, heightmap.end_position
+    );
+
+    // A path includes the square it starts on, which isn't a step
+    let steps_from_start = heightmap.get_path_from_start().len() - 1;
+    let steps_from_any_a = heightmap.get_path_from_any_a().len() - 1;
+
+    (steps_from_start, steps_from_any_a)
+}
+
+/// Fill the height matrix from the input, and return the (start, end) positions
+fn process_input(height_matrix: &mut Vec<Vec<usize>>, input_string: &str) -> (Position, Position) {
+    // S has elevation a, and E has elevation z
+    let mut start_position: Option<Position> = None;
+    let mut end_position: Option<Position> = None;
+
+    for input_file_line in input_string.lines() {
+        // Call trim() to ensure whitespace on the ends are removed
+        let input_file_line = input_file_line.trim();
+        if input_file_line.is_empty() {
+            continue;
+        }
+
+        let row_index = height_matrix.len();
+        let mut line_data_vector: Vec<usize> = Vec::with_capacity(input_file_line.len());
+
+        for (column_index, height_byte) in input_file_line.bytes().enumerate() {
+            let height_byte = match height_byte {
+                b'S' => {
+                    start_position = Some((row_index, column_index));
+                    b'a'
+                }
+                b'E' => {
+                    end_position = Some((row_index, column_index));
+                    b'z'
+                }
+                b'a'..=b'z' => height_byte,
+                strange_value => panic!(
+                    "Could not parse square elevation! Found: {}",
+                    strange_value as char
+                ),
+            };
+            line_data_vector.push((height_byte - b'a') as usize);
+        }
+
+        height_matrix.push(line_data_vector);
+    }
+
+    (
+        start_position.expect("Heightmap has no start position S"),
+        end_position.expect("Heightmap has no end position E"),
+    )
+}
+
+/// Draw the path over the map. Each square on the path shows the direction of the next step,
+/// the end is marked with E, and every other square is a '.'
+fn get_path_map(heightmap: &Heightmap, path: &[Position]) -> Vec<String> {
+    let mut path_map: Vec<Vec<char>> = heightmap
+        .height_matrix
+        .iter()
+        .map(|height_row| vec!['.'; height_row.len()])
+        .collect();
+
+    for step in path.windows(2) {
+        let (row_index, column_index) = step[0];
+        let (next_row_index, next_column_index) = step[1];
+
+        path_map[row_index][column_index] = if next_row_index < row_index {
+            '^'
+        } else if next_row_index > row_index {
+            'v'
+        } else if next_column_index < column_index {
+            '<'
+        } else {
+            '>'
+        };
+    }
+
+    let (end_row_index, end_column_index) = heightmap.end_position;
+    path_map[end_row_index][end_column_index] = 'E';
+
+    path_map
+        .into_iter()
+        .map(|path_row| path_row.into_iter().collect())
+        .collect()
+}
+
+/// Print the answers, the time taken to compute each of them, and the part 1 path
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let heightmap = Heightmap::new(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let path_from_start = heightmap.get_path_from_start();
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let path_from_any_a = heightmap.get_path_from_any_a();
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", path_from_start.len() - 1);
+    println!("answer\tPart 2\t{}", path_from_any_a.len() - 1);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    // '#' for a square on the path, '.' for any other
+    println!("grid\tPath from S to E");
+    for map_line in get_path_map(&heightmap, &path_from_start) {
+        let grid_line: String = map_line
+            .chars()
+            .map(|square| if square == '.' { '.' } else { '#' })
+            .collect();
+        println!("{}", grid_line);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (31, 29));
+    }
+
+    #[test]
+    fn process_input_finds_start_and_end() {
+        let mut height_matrix: Vec<Vec<usize>> = Vec::new();
+        let (start_position, end_position) = process_input(&mut height_matrix, "Sbc\nzEa\n");
+
+        assert_eq!(start_position, (0, 0));
+        assert_eq!(end_position, (1, 1));
+        assert_eq!(height_matrix, vec![vec![0, 1, 2], vec![25, 25, 0]]);
+    }
+
+    #[test]
+    fn neighbours_climb_at_most_one() {
+        let heightmap = Heightmap::new("Sbd\naaE\n");
+
+        // 'b' can step down to 'a' either side, but not up to 'd'
+        assert_eq!(heightmap.get_neighbours(&(0, 1)), vec![(1, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn example_path_map() {
+        let heightmap = Heightmap::new(&input::read_input("example.txt"));
+        let path_map = get_path_map(&heightmap, &heightmap.get_path_from_start());
+
+        // Not quite the picture in the puzzle text, but another of the shortest paths, with 31 steps
+        assert_eq!(
+            path_map,
+            vec!["v..v<<<<", ">v.vv<<^", ".v.v>E^^", ".>v>>>^^", "..>>>>>^"]
+        );
+    }
+}
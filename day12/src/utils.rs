@@ -0,0 +1,95 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use log::trace;
+
+/// Breadth-first search from any of the start nodes, until a node satisfying `is_goal` is reached.
+/// Every edge costs the same, so the first goal node found is at the shortest distance from the closest start node.
+/// Returns the path from that start node to the goal, both included, or None if no goal is reachable
+pub fn bfs<N, I>(
+    start_nodes: impl IntoIterator<Item = N>,
+    is_goal: impl Fn(&N) -> bool,
+    get_neighbours: impl Fn(&N) -> I,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // Each visited node points back to the node we reached it from. Start nodes point to None
+    let mut came_from: HashMap<N, Option<N>> = HashMap::new();
+    let mut search_queue: VecDeque<N> = VecDeque::new();
+
+    for start_node in start_nodes {
+        if came_from.insert(start_node.clone(), None).is_none() {
+            search_queue.push_back(start_node);
+        }
+    }
+
+    while let Some(current_node) = search_queue.pop_front() {
+        if is_goal(&current_node) {
+            trace!("Goal reached after visiting {} nodes", came_from.len());
+            return Some(get_path(&came_from, current_node));
+        }
+
+        for neighbour_node in get_neighbours(&current_node) {
+            if came_from.contains_key(&neighbour_node) {
+                continue;
+            }
+
+            came_from.insert(neighbour_node.clone(), Some(current_node.clone()));
+            search_queue.push_back(neighbour_node);
+        }
+    }
+
+    trace!("No goal reachable after visiting {} nodes", came_from.len());
+    None
+}
+
+/// Walk back from the goal node to its start node, and return the path in start to goal order
+fn get_path<N: Clone + Eq + Hash>(came_from: &HashMap<N, Option<N>>, goal_node: N) -> Vec<N> {
+    let mut path = vec![goal_node];
+
+    while let Some(Some(previous_node)) = came_from.get(path.last().expect("Path is never empty")) {
+        path.push(previous_node.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Neighbours on a number line, limited to 0..=10
+    fn get_number_line_neighbours(number: &i32) -> Vec<i32> {
+        [number - 1, number + 1]
+            .into_iter()
+            .filter(|neighbour| (0..=10).contains(neighbour))
+            .collect()
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let path = bfs([2], |&number| number == 6, get_number_line_neighbours);
+        assert_eq!(path, Some(vec![2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn bfs_starts_from_closest_start_node() {
+        let path = bfs([0, 9], |&number| number == 6, get_number_line_neighbours);
+        assert_eq!(path, Some(vec![9, 8, 7, 6]));
+    }
+
+    #[test]
+    fn bfs_start_node_can_be_goal() {
+        let path = bfs([4], |&number| number == 4, get_number_line_neighbours);
+        assert_eq!(path, Some(vec![4]));
+    }
+
+    #[test]
+    fn bfs_unreachable_goal() {
+        let path = bfs([4], |&number| number == 11, get_number_line_neighbours);
+        assert_eq!(path, None);
+    }
+}
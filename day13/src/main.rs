@@ -0,0 +1,234 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::time::Instant;
+
+mod packet;
+use packet::{Packet, PacketParseError};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (ordered_index_sum, decoder_key) = match solve(INPUT_FILENAME) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("Could not read packets from {}: {}", INPUT_FILENAME, err);
+            std::process::exit(1);
+        }
+    };
+
+    println!(
+        "Part 1 | What is the sum of the indices of the pairs that are already in the right order?\nAnswer: {}",
+        ordered_index_sum
+    );
+    println!(
+        "Part 2 | What is the decoder key for the distress signal?\nAnswer: {}",
+        decoder_key
+    );
+}
+
+/// Parse the packets in the input file, and return the answers to both parts
+fn solve(input_file_name: &str) -> Result<(usize, usize), PacketParseError> {
+    let input_string = input::read_input(input_file_name);
+    let packets = parse_packets(&input_string)?;
+    debug!("Parsed {} packets", packets.len());
+
+    Ok((get_ordered_index_sum(&packets), get_decoder_key(packets)))
+}
+
+/// Parse every non-empty line as a packet. Blank lines only separate the pairs, so they're skipped.
+/// Part 1 compares the packets in pairs, so there has to be an even number of them
+fn parse_packets(input_string: &str) -> Result<Vec<Packet>, PacketParseError> {
+    let packets = input_string
+        .lines()
+        .map(|input_line| input_line.trim())
+        .filter(|input_line| !input_line.is_empty())
+        .map(|input_line| input_line.parse::<Packet>())
+        .collect::<Result<Vec<Packet>, PacketParseError>>()?;
+
+    if packets.len() % 2 != 0 {
+        return Err(PacketParseError::UnpairedPacket(packets.len()));
+    }
+    Ok(packets)
+}
+
+/// Part 1 - Sum the 1-based indices of the pairs whose left packet is not bigger than the right one
+fn get_ordered_index_sum(packets: &[Packet]) -> usize {
+    packets
+        .chunks_exact(2)
+        .enumerate()
+        .filter(|(_, packet_pair)| {
+            let is_ordered = packet_pair[0] <= packet_pair[1];
+            trace!(
+                "{} vs {} is in the right order: {}",
+                packet_pair[0],
+                packet_pair[1],
+                is_ordered
+            );
+            is_ordered
+        })
+        .map(|(pair_index, _)| pair_index + 1)
+        .sum()
+}
+
+/// Part 2 - Sort all the packets along with the two divider packets, and multiply the dividers' 1-based indices
+fn get_decoder_key(packets: Vec<Packet>) -> usize {
+    let divider_packets: [Packet; 2] = [
+        "[[2]]".parse().expect("Divider packet is valid"),
+        "[[6]]".parse().expect("Divider packet is valid"),
+    ];
+
+    // Packets like [2] compare equal to a divider without being one, so the dividers are tagged
+    // and found by their tag after sorting, rather than by searching for them
+    let mut tagged_packets: Vec<(Packet, bool)> = packets
+        .into_iter()
+        .map(|packet| (packet, false))
+        .chain(
+            divider_packets
+                .into_iter()
+                .map(|divider_packet| (divider_packet, true)),
+        )
+        .collect();
+    // A stable sort, so each divider stays after every packet that compares equal to it
+    tagged_packets.sort_by(|(packet_a, _), (packet_b, _)| packet_a.cmp(packet_b));
+
+    tagged_packets
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, is_divider))| *is_divider)
+        .map(|(packet_index, _)| packet_index + 1)
+        .product()
+}
+
+/// Print the answers and the time taken to compute each of them in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let input_string = input::read_input(input_file_name);
+    let packets = match parse_packets(&input_string) {
+        Ok(packets) => packets,
+        Err(err) => {
+            eprintln!("Could not read packets from {}: {}", input_file_name, err);
+            std::process::exit(1);
+        }
+    };
+
+    let part1_start = Instant::now();
+    let ordered_index_sum = get_ordered_index_sum(&packets);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let decoder_key = get_decoder_key(packets);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", ordered_index_sum);
+    println!("answer\tPart 2\t{}", decoder_key);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(packet_str: &str) -> Packet {
+        packet_str.parse().unwrap()
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), Ok((13, 140)));
+    }
+
+    #[test]
+    fn parse_nested_packet() {
+        assert_eq!(
+            packet("[[1],4,[]]"),
+            Packet::List(vec![
+                Packet::List(vec![Packet::Integer(1)]),
+                Packet::Integer(4),
+                Packet::List(vec![]),
+            ])
+        );
+        assert_eq!(packet("[10,[2]]").to_string(), "[10,[2]]");
+    }
+
+    #[test]
+    fn example_pair_ordering() {
+        let packets = parse_packets(&input::read_input("example.txt")).unwrap();
+        let is_ordered: Vec<bool> = packets
+            .chunks_exact(2)
+            .map(|packet_pair| packet_pair[0] < packet_pair[1])
+            .collect();
+
+        assert_eq!(
+            is_ordered,
+            vec![true, true, false, true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn integer_compares_as_single_item_list() {
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        // In the right order both ways, so equal, even though they aren't written the same way
+        assert_eq!(
+            packet("[[5]]").cmp(&packet("[5]")),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(packet("[[5]]"), packet("[5]"));
+        assert_ne!(packet("[[5]]"), packet("[5,5]"));
+    }
+
+    #[test]
+    fn packets_equal_to_a_divider_are_not_dividers() {
+        // [2] and [[[6]]] compare equal to the dividers, and sort just before them
+        let packets = ["[1]", "[2]", "[[[6]]]", "[7]"].map(packet).to_vec();
+
+        assert_eq!(get_decoder_key(packets), 3 * 5);
+    }
+
+    #[test]
+    fn malformed_packets_give_errors() {
+        assert_eq!(
+            "[1,[2]".parse::<Packet>(),
+            Err(PacketParseError::UnclosedList)
+        );
+        assert_eq!(
+            "[1]]".parse::<Packet>(),
+            Err(PacketParseError::ExpectedSeparator(']', 3))
+        );
+        assert_eq!(
+            "[1,]".parse::<Packet>(),
+            Err(PacketParseError::UnexpectedCharacter(']', 3))
+        );
+        assert_eq!(
+            "[1 2]".parse::<Packet>(),
+            Err(PacketParseError::ExpectedSeparator(' ', 2))
+        );
+        assert_eq!(
+            "[99999999999]".parse::<Packet>(),
+            Err(PacketParseError::IntegerTooLarge(1))
+        );
+        assert_eq!("7".parse::<Packet>(), Err(PacketParseError::NotAList));
+        assert_eq!("".parse::<Packet>(), Err(PacketParseError::NotAList));
+    }
+
+    #[test]
+    fn unpaired_packet_gives_an_error() {
+        assert_eq!(
+            parse_packets("[1]\n[2]\n\n[3]\n"),
+            Err(PacketParseError::UnpairedPacket(3))
+        );
+        assert_eq!(
+            parse_packets("[1]\n[2]\n").map(|packets| packets.len()),
+            Ok(2)
+        );
+    }
+}
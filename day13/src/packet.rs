@@ -0,0 +1,185 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// A packet value is either an integer, or a list of zero or more values
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+/// Equal means in the right order both ways, to agree with Ord. So [[5]] and [5] are equal,
+/// even though they aren't written the same way
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl Ord for Packet {
+    /// Refer: https://adventofcode.com/2022/day/13
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            // The lower integer should come first
+            (Self::Integer(left), Self::Integer(right)) => left.cmp(right),
+            // Compare values one by one. If one list runs out first, it should come first.
+            // This is exactly how slices compare
+            (Self::List(left), Self::List(right)) => left.as_slice().cmp(right.as_slice()),
+            // Exactly one value is an integer. Convert it to a list containing only that integer and retry
+            (Self::Integer(_), Self::List(right)) => {
+                std::slice::from_ref(self).cmp(right.as_slice())
+            }
+            (Self::List(left), Self::Integer(_)) => {
+                left.as_slice().cmp(std::slice::from_ref(other))
+            }
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Packet {
+    /// Write the packet back out in the same bracketed syntax it was parsed from
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::List(values) => {
+                write!(f, "[")?;
+                for (value_index, value) in values.iter().enumerate() {
+                    if value_index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PacketParseError {
+    /// The input ended while a list was still open
+    UnclosedList,
+    /// Anything other than an integer or a list where a value should be, like the ']' in "[1,]". Holds the byte offset
+    UnexpectedCharacter(char, usize),
+    /// Something other than ',' or ']' after a value inside a list, or more input after the packet ended
+    ExpectedSeparator(char, usize),
+    IntegerTooLarge(usize),
+    /// Every packet is a list, so an empty line or a lone integer is not a packet
+    NotAList,
+    /// Packets come in pairs, so an odd number of them leaves the last one without a partner. Holds the packet count
+    UnpairedPacket(usize),
+}
+
+impl fmt::Display for PacketParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnclosedList => write!(f, "packet ended with a list still open"),
+            Self::UnexpectedCharacter(character, offset) => {
+                write!(f, "unexpected '{}' at offset {}", character, offset)
+            }
+            Self::ExpectedSeparator(character, offset) => write!(
+                f,
+                "expected ',' or ']' but found '{}' at offset {}",
+                character, offset
+            ),
+            Self::IntegerTooLarge(offset) => {
+                write!(f, "integer at offset {} is too large", offset)
+            }
+            Self::NotAList => write!(f, "packet is not a list"),
+            Self::UnpairedPacket(packet_count) => write!(
+                f,
+                "{} packets can't be split into pairs, the last one has no partner",
+                packet_count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PacketParseError {}
+
+impl FromStr for Packet {
+    type Err = PacketParseError;
+
+    fn from_str(packet_str: &str) -> Result<Self, Self::Err> {
+        let packet_bytes = packet_str.trim().as_bytes();
+        if packet_bytes.first() != Some(&b'[') {
+            return Err(PacketParseError::NotAList);
+        }
+
+        let (packet, end_offset) = parse_value(packet_bytes, 0)?;
+
+        if let Some(&trailing_byte) = packet_bytes.get(end_offset) {
+            return Err(PacketParseError::ExpectedSeparator(
+                trailing_byte as char,
+                end_offset,
+            ));
+        }
+
+        Ok(packet)
+    }
+}
+
+/// Parse the value starting at the offset, and return it along with the offset just past its end
+fn parse_value(packet_bytes: &[u8], offset: usize) -> Result<(Packet, usize), PacketParseError> {
+    match packet_bytes.get(offset) {
+        None => Err(PacketParseError::UnclosedList),
+        Some(b'[') => parse_list(packet_bytes, offset),
+        Some(b'0'..=b'9') => parse_integer(packet_bytes, offset),
+        Some(&strange_byte) => Err(PacketParseError::UnexpectedCharacter(
+            strange_byte as char,
+            offset,
+        )),
+    }
+}
+
+/// The offset points at the list's opening '['
+fn parse_list(packet_bytes: &[u8], offset: usize) -> Result<(Packet, usize), PacketParseError> {
+    let mut values: Vec<Packet> = Vec::new();
+    let mut offset = offset + 1;
+
+    // Empty list
+    if packet_bytes.get(offset) == Some(&b']') {
+        return Ok((Packet::List(values), offset + 1));
+    }
+
+    loop {
+        let (value, value_end_offset) = parse_value(packet_bytes, offset)?;
+        values.push(value);
+
+        match packet_bytes.get(value_end_offset) {
+            None => return Err(PacketParseError::UnclosedList),
+            Some(b',') => offset = value_end_offset + 1,
+            Some(b']') => return Ok((Packet::List(values), value_end_offset + 1)),
+            Some(&strange_byte) => {
+                return Err(PacketParseError::ExpectedSeparator(
+                    strange_byte as char,
+                    value_end_offset,
+                ))
+            }
+        }
+    }
+}
+
+/// The offset points at the integer's first digit
+fn parse_integer(packet_bytes: &[u8], offset: usize) -> Result<(Packet, usize), PacketParseError> {
+    let digit_count = packet_bytes[offset..]
+        .iter()
+        .take_while(|packet_byte| packet_byte.is_ascii_digit())
+        .count();
+    let end_offset = offset + digit_count;
+
+    // Only ASCII digits were taken, so this is always valid UTF-8 and can only fail on overflow
+    let value = std::str::from_utf8(&packet_bytes[offset..end_offset])
+        .expect("ASCII digits are valid UTF-8")
+        .parse::<u32>()
+        .map_err(|_| PacketParseError::IntegerTooLarge(offset))?;
+
+    Ok((Packet::Integer(value), end_offset))
+}
@@ -0,0 +1,135 @@
+use aoc_common::input;
+use log::debug;
+use std::time::Instant;
+
+mod utils;
+use utils::{Cave, FloorType};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (abyss_sand_count, floor_sand_count) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | How many units of sand come to rest before sand starts flowing into the abyss below?\nAnswer: {}",
+        abyss_sand_count
+    );
+    println!(
+        "Part 2 | How many units of sand come to rest before the source of the sand becomes blocked?\nAnswer: {}",
+        floor_sand_count
+    );
+
+    // Draw the cave once it's full of sand, for both variants
+    if std::env::args().any(|arg| arg == "--render") {
+        let cave = Cave::new(&input::read_input(INPUT_FILENAME));
+
+        for floor_type in [FloorType::Abyss, FloorType::Floor] {
+            let mut filled_cave = cave.clone();
+            filled_cave.fill_with_sand(floor_type);
+
+            println!("\nCave with {:?}:", floor_type);
+            for cave_line in filled_cave.render(floor_type) {
+                println!("{}", cave_line);
+            }
+        }
+    }
+}
+
+/// Count the sand that comes to rest with the abyss below the cave, and with a floor
+fn solve(input_file_name: &str) -> (usize, usize) {
+    let cave = Cave::new(&input::read_input(input_file_name));
+    debug!(
+        "Cave has {} rock tiles, the lowest at y = {}",
+        cave.tiles.len(),
+        cave.lowest_rock_y
+    );
+
+    let abyss_sand_count = cave.clone().fill_with_sand(FloorType::Abyss);
+    let floor_sand_count = cave.clone().fill_with_sand(FloorType::Floor);
+
+    (abyss_sand_count, floor_sand_count)
+}
+
+/// Print the answers, the time taken to compute each of them, and where the sand ends up with a floor
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let cave = Cave::new(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let abyss_sand_count = cave.clone().fill_with_sand(FloorType::Abyss);
+    let part1_time = part1_start.elapsed();
+
+    let mut floor_cave = cave;
+    let part2_start = Instant::now();
+    let floor_sand_count = floor_cave.fill_with_sand(FloorType::Floor);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", abyss_sand_count);
+    println!("answer\tPart 2\t{}", floor_sand_count);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    // '#' for resting sand, '.' for everything else
+    println!("grid\tSand at rest on the cave floor");
+    for cave_line in floor_cave.render(FloorType::Floor) {
+        let grid_line: String = cave_line
+            .chars()
+            .map(|tile| if tile == 'o' { '#' } else { '.' })
+            .collect();
+        println!("{}", grid_line);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (24, 93));
+    }
+
+    #[test]
+    fn example_render_with_abyss() {
+        let mut cave = Cave::new(&input::read_input("example.txt"));
+        cave.fill_with_sand(FloorType::Abyss);
+
+        // Same as the picture in the puzzle text
+        assert_eq!(
+            cave.render(FloorType::Abyss),
+            vec![
+                "......+...",
+                "..........",
+                "......o...",
+                ".....ooo..",
+                "....#ooo##",
+                "...o#ooo#.",
+                "..###ooo#.",
+                "....oooo#.",
+                ".o.ooooo#.",
+                "#########.",
+            ]
+        );
+    }
+
+    #[test]
+    fn example_render_with_floor() {
+        let mut cave = Cave::new(&input::read_input("example.txt"));
+        cave.fill_with_sand(FloorType::Floor);
+
+        let cave_render = cave.render(FloorType::Floor);
+        assert_eq!(cave_render[0], "..........o..........");
+        assert_eq!(cave_render[10], "ooooo.......ooooooooo");
+        assert_eq!(cave_render[11], "~~~~~~~~~~~~~~~~~~~~~");
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use log::trace;
+
+/// Sand pours into the cave from this point
+pub const SAND_SOURCE: [i32; 2] = [500, 0];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+/// What's below the lowest rock
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FloorType {
+    /// Part 1 - Nothing. Sand falling past the lowest rock flows into the abyss forever
+    Abyss,
+    /// Part 2 - An infinite horizontal floor two below the lowest rock
+    Floor,
+}
+
+/// Points are [x, y] like day9's rope knots, except y grows downwards, the way the puzzle's scan does.
+/// Only rock and sand are stored, so the cave can be as wide as it likes
+#[derive(Debug, Clone)]
+pub struct Cave {
+    pub tiles: HashMap<[i32; 2], Tile>,
+    pub lowest_rock_y: i32,
+}
+
+impl Cave {
+    /// Every line is a rock path. Example: "498,4 -> 498,6 -> 496,6"
+    pub fn new(input_string: &str) -> Cave {
+        let mut tiles: HashMap<[i32; 2], Tile> = HashMap::new();
+
+        for input_line in input_string.lines() {
+            let input_line = input_line.trim();
+            if input_line.is_empty() {
+                continue;
+            }
+
+            let path_points: Vec<[i32; 2]> = input_line.split(" -> ").map(parse_point).collect();
+            trace!("Rock path: {:?}", path_points);
+
+            for path_segment in path_points.windows(2) {
+                for rock_point in get_segment_points(path_segment[0], path_segment[1]) {
+                    tiles.insert(rock_point, Tile::Rock);
+                }
+            }
+        }
+
+        let lowest_rock_y = tiles
+            .keys()
+            .map(|rock_point| rock_point[1])
+            .max()
+            .expect("Cave has no rock paths");
+
+        Cave {
+            tiles,
+            lowest_rock_y,
+        }
+    }
+
+    fn is_blocked(&self, point: [i32; 2], floor_type: FloorType) -> bool {
+        if floor_type == FloorType::Floor && point[1] == self.lowest_rock_y + 2 {
+            return true;
+        }
+
+        self.tiles.contains_key(&point)
+    }
+
+    /// Drop a single grain of sand from the source, and return where it comes to rest.
+    /// None if it falls into the abyss, or if the source is already blocked
+    pub fn drop_sand(&mut self, floor_type: FloorType) -> Option<[i32; 2]> {
+        if self.is_blocked(SAND_SOURCE, floor_type) {
+            return None;
+        }
+
+        let mut sand_point = SAND_SOURCE;
+
+        'falling: loop {
+            // Nothing below the lowest rock can stop the sand, unless there's a floor
+            if floor_type == FloorType::Abyss && sand_point[1] > self.lowest_rock_y {
+                return None;
+            }
+
+            // Down, then down-left, then down-right
+            for x_offset in [0, -1, 1] {
+                let next_point = [sand_point[0] + x_offset, sand_point[1] + 1];
+                if !self.is_blocked(next_point, floor_type) {
+                    sand_point = next_point;
+                    continue 'falling;
+                }
+            }
+
+            self.tiles.insert(sand_point, Tile::Sand);
+            return Some(sand_point);
+        }
+    }
+
+    /// Keep dropping sand until a grain doesn't come to rest, and return how many did
+    pub fn fill_with_sand(&mut self, floor_type: FloorType) -> usize {
+        let mut resting_sand_count = 0;
+
+        while let Some(sand_point) = self.drop_sand(floor_type) {
+            trace!("Sand came to rest at {:?}", sand_point);
+            resting_sand_count += 1;
+        }
+
+        resting_sand_count
+    }
+
+    /// Draw the cave the way the puzzle text does. '#' is rock, 'o' is sand, '+' is the sand source,
+    /// and '~' is the floor, which is only drawn as wide as everything else
+    pub fn render(&self, floor_type: FloorType) -> Vec<String> {
+        let all_x = self
+            .tiles
+            .keys()
+            .map(|point| point[0])
+            .chain(std::iter::once(SAND_SOURCE[0]));
+        let min_x = all_x.clone().min().unwrap_or(SAND_SOURCE[0]);
+        let max_x = all_x.max().unwrap_or(SAND_SOURCE[0]);
+
+        let max_y = match floor_type {
+            FloorType::Abyss => self.lowest_rock_y,
+            FloorType::Floor => self.lowest_rock_y + 2,
+        };
+
+        (SAND_SOURCE[1]..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| match self.tiles.get(&[x, y]) {
+                        Some(Tile::Rock) => '#',
+                        Some(Tile::Sand) => 'o',
+                        None if [x, y] == SAND_SOURCE => '+',
+                        None if self.is_blocked([x, y], floor_type) => '~',
+                        None => '.',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// "498,4" -> [498, 4]
+fn parse_point(point_str: &str) -> [i32; 2] {
+    let (x_str, y_str) = point_str
+        .trim()
+        .split_once(',')
+        .expect("Could not split point into x and y");
+
+    [
+        x_str.parse::<i32>().expect("Could not parse x coordinate"),
+        y_str.parse::<i32>().expect("Could not parse y coordinate"),
+    ]
+}
+
+/// Every point on a straight horizontal or vertical line, both ends included
+fn get_segment_points(start_point: [i32; 2], end_point: [i32; 2]) -> Vec<[i32; 2]> {
+    assert!(
+        start_point[0] == end_point[0] || start_point[1] == end_point[1],
+        "Rock path segment {:?} -> {:?} is not a straight line",
+        start_point,
+        end_point
+    );
+
+    let step = [
+        (end_point[0] - start_point[0]).signum(),
+        (end_point[1] - start_point[1]).signum(),
+    ];
+
+    let mut segment_points = vec![start_point];
+    let mut current_point = start_point;
+    while current_point != end_point {
+        current_point = [current_point[0] + step[0], current_point[1] + step[1]];
+        segment_points.push(current_point);
+    }
+
+    segment_points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_points_go_both_ways() {
+        assert_eq!(
+            get_segment_points([498, 4], [498, 6]),
+            vec![[498, 4], [498, 5], [498, 6]]
+        );
+        assert_eq!(
+            get_segment_points([498, 6], [496, 6]),
+            vec![[498, 6], [497, 6], [496, 6]]
+        );
+        assert_eq!(get_segment_points([1, 1], [1, 1]), vec![[1, 1]]);
+    }
+
+    #[test]
+    fn first_grains_rest_as_in_puzzle_text() {
+        let mut cave = Cave::new(&std::fs::read_to_string("example.txt").unwrap());
+
+        assert_eq!(cave.lowest_rock_y, 9);
+        assert_eq!(cave.drop_sand(FloorType::Abyss), Some([500, 8]));
+        assert_eq!(cave.drop_sand(FloorType::Abyss), Some([499, 8]));
+        assert_eq!(cave.drop_sand(FloorType::Abyss), Some([501, 8]));
+    }
+
+    #[test]
+    fn floor_stops_sand_two_below_lowest_rock() {
+        // A single rock, so the sand falls straight down beside it
+        let mut cave = Cave::new("502,3 -> 502,3");
+
+        assert_eq!(cave.drop_sand(FloorType::Abyss), None);
+        assert_eq!(cave.drop_sand(FloorType::Floor), Some([500, 4]));
+    }
+}
@@ -0,0 +1,193 @@
+use aoc_common::input;
+use aoc_common::intervals::{get_covered_size, merge_intervals};
+use log::{debug, trace};
+use std::time::Instant;
+
+mod utils;
+use utils::Sensor;
+
+const INPUT_FILENAME: &str = "example.txt";
+
+// The example asks about row 10 and a 0..=20 search area. The real puzzle uses row 2_000_000 and 0..=4_000_000.
+// Override with --row <y> and --bound <max> when running against a puzzle input
+const DEFAULT_TARGET_ROW: i64 = 10;
+const DEFAULT_SEARCH_BOUND: i64 = 20;
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    let target_row = get_arg_or_exit(get_arg_value("--row")).unwrap_or(DEFAULT_TARGET_ROW);
+    let search_bound = get_arg_or_exit(get_arg_value("--bound")).unwrap_or(DEFAULT_SEARCH_BOUND);
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME, target_row, search_bound);
+        return;
+    }
+
+    let (covered_position_count, tuning_frequency) =
+        solve(INPUT_FILENAME, target_row, search_bound);
+
+    println!(
+        "Part 1 | In the row where y={}, how many positions cannot contain a beacon?\nAnswer: {}",
+        target_row, covered_position_count
+    );
+    println!(
+        "Part 2 | Find the only possible position for the distress beacon. What is its tuning frequency?\nAnswer: {}",
+        tuning_frequency
+    );
+}
+
+/// The flag's value, or print what's wrong with it and exit
+fn get_arg_or_exit<T>(arg_result: Result<Option<T>, String>) -> Option<T> {
+    arg_result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    })
+}
+
+/// Read the number following a flag, like the 2000000 in "--row 2000000". None if the flag isn't given
+fn get_arg_value(flag: &str) -> Result<Option<i64>, String> {
+    let args: Vec<String> = std::env::args().collect();
+
+    let Some(flag_index) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    let Some(value_str) = args.get(flag_index + 1) else {
+        return Err(format!("{} needs a value", flag));
+    };
+
+    value_str
+        .parse::<i64>()
+        .map(Some)
+        .map_err(|_| format!("Could not parse {} value {}", flag, value_str))
+}
+
+fn solve(input_file_name: &str, target_row: i64, search_bound: i64) -> (usize, i64) {
+    let sensors = parse_sensors(&input::read_input(input_file_name));
+    debug!("Parsed {} sensors", sensors.len());
+
+    (
+        get_covered_position_count(&sensors, target_row),
+        get_tuning_frequency(&sensors, search_bound),
+    )
+}
+
+fn parse_sensors(input_string: &str) -> Vec<Sensor> {
+    input_string
+        .lines()
+        .filter(|input_line| !input_line.trim().is_empty())
+        .map(Sensor::new)
+        .collect()
+}
+
+/// Every sensor's coverage of the row, merged together
+fn get_row_coverage(sensors: &[Sensor], row_y: i64) -> Vec<(i64, i64)> {
+    merge_intervals(
+        sensors
+            .iter()
+            .filter_map(|sensor| sensor.get_row_interval(row_y))
+            .collect(),
+    )
+}
+
+/// Part 1 - Positions on the row that are within some sensor's radius, and so can't hold an unknown beacon.
+/// Beacons we already know about on the row don't count
+fn get_covered_position_count(sensors: &[Sensor], target_row: i64) -> usize {
+    let row_coverage = get_row_coverage(sensors, target_row);
+    trace!("Row {} coverage: {:?}", target_row, row_coverage);
+
+    let mut known_beacons_on_row: Vec<i64> = sensors
+        .iter()
+        .filter(|sensor| sensor.closest_beacon[1] == target_row)
+        .map(|sensor| sensor.closest_beacon[0])
+        .collect();
+    // Many sensors can share the same closest beacon
+    known_beacons_on_row.sort_unstable();
+    known_beacons_on_row.dedup();
+
+    // Every known beacon is within its own sensor's radius, so it is always inside the coverage
+    get_covered_size(&row_coverage) - known_beacons_on_row.len()
+}
+
+/// Part 2 - There's exactly one position with 0 <= x, y <= search_bound that no sensor covers.
+/// Check each row for a gap in its coverage, once clipped to the search area
+fn get_tuning_frequency(sensors: &[Sensor], search_bound: i64) -> i64 {
+    for row_y in 0..=search_bound {
+        let clipped_intervals: Vec<(i64, i64)> = sensors
+            .iter()
+            .filter_map(|sensor| sensor.get_row_interval(row_y))
+            .filter(|&(interval_start, interval_end)| {
+                interval_end >= 0 && interval_start <= search_bound
+            })
+            .map(|(interval_start, interval_end)| {
+                (interval_start.max(0), interval_end.min(search_bound))
+            })
+            .collect();
+
+        let row_coverage = merge_intervals(clipped_intervals);
+
+        // A fully covered row merges into the single interval (0, search_bound)
+        if row_coverage == [(0, search_bound)] {
+            continue;
+        }
+
+        let distress_x = match row_coverage.first() {
+            Some(&(0, first_interval_end)) => first_interval_end + 1,
+            _ => 0,
+        };
+        debug!("Distress beacon found at x={}, y={}", distress_x, row_y);
+
+        return distress_x * 4_000_000 + row_y;
+    }
+
+    panic!("No uncovered position found in the search area!");
+}
+
+/// Print the answers and the time taken to compute each of them in the format read by the report crate
+fn print_report(input_file_name: &str, target_row: i64, search_bound: i64) {
+    let sensors = parse_sensors(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let covered_position_count = get_covered_position_count(&sensors, target_row);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let tuning_frequency = get_tuning_frequency(&sensors, search_bound);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", covered_position_count);
+    println!("answer\tPart 2\t{}", tuning_frequency);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt", 10, 20), (26, 56000011));
+    }
+
+    #[test]
+    fn example_row_coverage() {
+        let sensors = parse_sensors(&input::read_input("example.txt"));
+
+        assert_eq!(get_row_coverage(&sensors, 10), vec![(-2, 24)]);
+        // The distress beacon's row has a one position gap
+        assert_eq!(get_row_coverage(&sensors, 11), vec![(-3, 13), (15, 25)]);
+    }
+
+    #[test]
+    fn gap_at_the_edge_of_the_search_area() {
+        // Row 0 is covered from x=0 to x=4, leaving x=5 free
+        let sensors = [Sensor::new(
+            "Sensor at x=2, y=0: closest beacon is at x=2, y=2",
+        )];
+
+        assert_eq!(get_tuning_frequency(&sensors, 5), 5 * 4_000_000);
+    }
+}
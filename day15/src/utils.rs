@@ -0,0 +1,88 @@
+use log::trace;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Sensor {
+    pub position: [i64; 2],
+    pub closest_beacon: [i64; 2],
+    /// Manhattan distance to the closest beacon. There can't be another beacon this close
+    pub radius: i64,
+}
+
+impl Sensor {
+    /// Example: "Sensor at x=2, y=18: closest beacon is at x=-2, y=15"
+    pub fn new(input_line: &str) -> Sensor {
+        let (sensor_str, beacon_str) = input_line
+            .trim()
+            .strip_prefix("Sensor at ")
+            .and_then(|line_rest| line_rest.split_once(": closest beacon is at "))
+            .expect("Could not split line into sensor and beacon");
+
+        let position = parse_point(sensor_str);
+        let closest_beacon = parse_point(beacon_str);
+
+        Sensor {
+            position,
+            closest_beacon,
+            radius: get_manhattan_distance(position, closest_beacon),
+        }
+    }
+
+    /// The x positions on this row within the sensor's radius, if it reaches the row at all
+    pub fn get_row_interval(&self, row_y: i64) -> Option<(i64, i64)> {
+        let remaining_radius = self.radius - (self.position[1] - row_y).abs();
+
+        if remaining_radius < 0 {
+            return None;
+        }
+
+        trace!(
+            "Sensor at {:?} covers {} either side on row {}",
+            self.position,
+            remaining_radius,
+            row_y
+        );
+        Some((
+            self.position[0] - remaining_radius,
+            self.position[0] + remaining_radius,
+        ))
+    }
+}
+
+pub fn get_manhattan_distance(point_a: [i64; 2], point_b: [i64; 2]) -> i64 {
+    (point_a[0] - point_b[0]).abs() + (point_a[1] - point_b[1]).abs()
+}
+
+/// "x=-2, y=15" -> [-2, 15]
+fn parse_point(point_str: &str) -> [i64; 2] {
+    let (x_str, y_str) = point_str
+        .split_once(", ")
+        .expect("Could not split point into x and y");
+
+    [
+        x_str
+            .trim_start_matches("x=")
+            .parse::<i64>()
+            .expect("Could not parse x coordinate"),
+        y_str
+            .trim_start_matches("y=")
+            .parse::<i64>()
+            .expect("Could not parse y coordinate"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sensor_reads_positions_and_radius() {
+        let sensor = Sensor::new("Sensor at x=8, y=7: closest beacon is at x=2, y=10");
+
+        assert_eq!(sensor.position, [8, 7]);
+        assert_eq!(sensor.closest_beacon, [2, 10]);
+        assert_eq!(sensor.radius, 9);
+        assert_eq!(sensor.get_row_interval(10), Some((2, 14)));
+        assert_eq!(sensor.get_row_interval(16), Some((8, 8)));
+        assert_eq!(sensor.get_row_interval(17), None);
+    }
+}
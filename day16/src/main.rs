@@ -0,0 +1,155 @@
+use aoc_common::input;
+use log::debug;
+use std::cmp::Reverse;
+use std::time::Instant;
+
+mod utils;
+use utils::{parse_valve, Valve, ValveNetwork};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (alone_pressure, with_elephant_pressure) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the most pressure you can release in 30 minutes?\nAnswer: {}",
+        alone_pressure
+    );
+    println!(
+        "Part 2 | With you and an elephant working together for 26 minutes, what is the most pressure you could release?\nAnswer: {}",
+        with_elephant_pressure
+    );
+}
+
+fn solve(input_file_name: &str) -> (u32, u32) {
+    let valve_network = get_valve_network(&input::read_input(input_file_name));
+
+    (
+        get_alone_pressure(&valve_network),
+        get_with_elephant_pressure(&valve_network),
+    )
+}
+
+fn get_valve_network(input_string: &str) -> ValveNetwork {
+    let valves: Vec<Valve> = input_string
+        .lines()
+        .filter(|input_line| !input_line.trim().is_empty())
+        .map(parse_valve)
+        .collect();
+    debug!("Scanned {} valves", valves.len());
+
+    ValveNetwork::new(&valves)
+}
+
+/// Part 1 - The best of every valve set we can open alone in 30 minutes
+fn get_alone_pressure(valve_network: &ValveNetwork) -> u32 {
+    valve_network
+        .get_best_pressure_per_valve_set(30)
+        .into_values()
+        .max()
+        .unwrap_or(0)
+}
+
+/// Part 2 - We and the elephant each get 26 minutes, and never open the same valve.
+/// So the answer is the best sum over two disjoint valve sets, each opened by a single agent
+fn get_with_elephant_pressure(valve_network: &ValveNetwork) -> u32 {
+    let best_pressure_per_valve_set = valve_network.get_best_pressure_per_valve_set(26);
+
+    // Sort the best sets first. Once a set can't beat the best pair even alongside the overall best set,
+    // no set after it can either
+    let mut valve_set_pressures: Vec<(u64, u32)> =
+        best_pressure_per_valve_set.into_iter().collect();
+    valve_set_pressures.sort_unstable_by_key(|&(_, pressure)| Reverse(pressure));
+
+    let overall_best_pressure = valve_set_pressures
+        .first()
+        .map_or(0, |&(_, pressure)| pressure);
+    let mut best_pair_pressure = 0;
+
+    for (my_index, &(my_valve_set, my_pressure)) in valve_set_pressures.iter().enumerate() {
+        if my_pressure + overall_best_pressure <= best_pair_pressure {
+            break;
+        }
+
+        for &(elephant_valve_set, elephant_pressure) in &valve_set_pressures[my_index..] {
+            if my_pressure + elephant_pressure <= best_pair_pressure {
+                break;
+            }
+
+            if my_valve_set & elephant_valve_set == 0 {
+                best_pair_pressure = my_pressure + elephant_pressure;
+            }
+        }
+    }
+
+    debug!("Best pressure with the elephant: {}", best_pair_pressure);
+    best_pair_pressure
+}
+
+/// Print the answers, the time taken to compute each of them, and the flow rates of the valves worth opening
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let input_string = input::read_input(input_file_name);
+
+    let part1_start = Instant::now();
+    let alone_pressure = get_alone_pressure(&get_valve_network(&input_string));
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let with_elephant_pressure = get_with_elephant_pressure(&get_valve_network(&input_string));
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", alone_pressure);
+    println!("answer\tPart 2\t{}", with_elephant_pressure);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    let mut working_valves: Vec<Valve> = input_string
+        .lines()
+        .filter(|input_line| !input_line.trim().is_empty())
+        .map(parse_valve)
+        .filter(|valve| valve.flow_rate > 0)
+        .collect();
+    working_valves.sort_unstable_by_key(|valve| Reverse(valve.flow_rate));
+
+    println!("bars\tFlow rate of each working valve");
+    for valve in working_valves {
+        println!("0\t{}\t{}", valve.name, valve.flow_rate);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (1651, 1707));
+    }
+
+    #[test]
+    fn example_best_valve_sets() {
+        let valve_network = get_valve_network(&input::read_input("example.txt"));
+        let best_pressure_per_valve_set = valve_network.get_best_pressure_per_valve_set(26);
+
+        // The puzzle text's split: we open JJ, BB and CC, the elephant opens DD, HH and EE.
+        // Bits follow the network's valve indexes, AA=0, BB=1, CC=2, DD=3, EE=4, HH=5, JJ=6
+        let my_valve_set = (1 << 6) | (1 << 1) | (1 << 2);
+        let elephant_valve_set = (1 << 3) | (1 << 5) | (1 << 4);
+        assert_eq!(
+            best_pressure_per_valve_set[&my_valve_set]
+                + best_pressure_per_valve_set[&elephant_valve_set],
+            1707
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use log::{debug, trace};
+
+/// Everyone starts at this valve
+pub const START_VALVE_NAME: &str = "AA";
+
+/// A valve as scanned, with names borrowed straight out of the input
+#[derive(Debug, PartialEq)]
+pub struct Valve<'a> {
+    pub name: &'a str,
+    pub flow_rate: u32,
+    pub tunnels: Vec<&'a str>,
+}
+
+/// Example: "Valve BB has flow rate=13; tunnels lead to valves CC, AA"
+/// A single tunnel is written "tunnel leads to valve GG"
+pub fn parse_valve(input_line: &str) -> Valve<'_> {
+    let (valve_str, tunnels_str) = input_line
+        .trim()
+        .split_once("; ")
+        .expect("Could not split line into valve and tunnels");
+
+    let (name, flow_rate_str) = valve_str
+        .strip_prefix("Valve ")
+        .and_then(|valve_rest| valve_rest.split_once(" has flow rate="))
+        .expect("Could not read valve name and flow rate");
+
+    let tunnels = tunnels_str
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| tunnels_str.strip_prefix("tunnel leads to valve "))
+        .expect("Could not read tunnel list")
+        .split(", ")
+        .collect();
+
+    Valve {
+        name,
+        flow_rate: flow_rate_str
+            .parse::<u32>()
+            .expect("Could not parse flow rate"),
+        tunnels,
+    }
+}
+
+/// The scanned valves, compressed down to the start valve plus the valves worth opening.
+/// Index 0 is always the start valve. The remaining indexes are the valve's bit in an opened valve set
+#[derive(Debug)]
+pub struct ValveNetwork {
+    pub flow_rates: Vec<u32>,
+    /// Minutes needed to walk from one valve to another, by the shortest route
+    pub distances: Vec<Vec<u32>>,
+}
+
+impl ValveNetwork {
+    pub fn new(valves: &[Valve]) -> ValveNetwork {
+        let valve_indexes: HashMap<&str, usize> = valves
+            .iter()
+            .enumerate()
+            .map(|(valve_index, valve)| (valve.name, valve_index))
+            .collect();
+
+        // Floyd-Warshall on the full scan. Valve counts are small enough that O(n^3) doesn't matter
+        let valve_count = valves.len();
+        let unreachable = u32::MAX / 2;
+        let mut full_distances = vec![vec![unreachable; valve_count]; valve_count];
+
+        for (valve_index, valve) in valves.iter().enumerate() {
+            full_distances[valve_index][valve_index] = 0;
+            for tunnel_name in &valve.tunnels {
+                let tunnel_index = *valve_indexes
+                    .get(tunnel_name)
+                    .unwrap_or_else(|| panic!("Tunnel leads to unknown valve {}", tunnel_name));
+                full_distances[valve_index][tunnel_index] = 1;
+            }
+        }
+
+        for via_index in 0..valve_count {
+            for from_index in 0..valve_count {
+                for to_index in 0..valve_count {
+                    let via_distance =
+                        full_distances[from_index][via_index] + full_distances[via_index][to_index];
+                    if via_distance < full_distances[from_index][to_index] {
+                        full_distances[from_index][to_index] = via_distance;
+                    }
+                }
+            }
+        }
+
+        // Jammed valves are only ever walked through, so they can be left out of the network
+        let start_index = *valve_indexes
+            .get(START_VALVE_NAME)
+            .expect("Scan has no start valve");
+        let kept_indexes: Vec<usize> = std::iter::once(start_index)
+            .chain(
+                valves
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, valve)| valve.flow_rate > 0)
+                    .map(|(valve_index, _)| valve_index),
+            )
+            .collect();
+
+        debug!(
+            "Compressed {} valves down to {} worth visiting",
+            valve_count,
+            kept_indexes.len()
+        );
+        assert!(
+            kept_indexes.len() <= 64,
+            "Too many valves with a flow rate to track as a bit set"
+        );
+
+        ValveNetwork {
+            flow_rates: kept_indexes
+                .iter()
+                .map(|&valve_index| valves[valve_index].flow_rate)
+                .collect(),
+            distances: kept_indexes
+                .iter()
+                .map(|&from_index| {
+                    kept_indexes
+                        .iter()
+                        .map(|&to_index| full_distances[from_index][to_index])
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// For every set of opened valves that a single agent can open in the time given,
+    /// the most pressure that can be released by opening exactly that set.
+    /// Bit n of the set is valve index n. The start valve's bit is never set
+    pub fn get_best_pressure_per_valve_set(&self, minutes: u32) -> HashMap<u64, u32> {
+        let mut best_pressure_per_valve_set: HashMap<u64, u32> = HashMap::new();
+        self.search(0, minutes, 0, 0, &mut best_pressure_per_valve_set);
+
+        trace!(
+            "{} distinct valve sets can be opened in {} minutes",
+            best_pressure_per_valve_set.len(),
+            minutes
+        );
+        best_pressure_per_valve_set
+    }
+
+    /// Depth first search over the order in which valves are opened.
+    /// Walking to a valve and opening it is a single move, so no time is spent on valves we won't open.
+    /// Only the best pressure per opened set is kept, which is all both parts need
+    fn search(
+        &self,
+        current_index: usize,
+        minutes_left: u32,
+        opened_valve_set: u64,
+        released_pressure: u32,
+        best_pressure_per_valve_set: &mut HashMap<u64, u32>,
+    ) {
+        let best_pressure = best_pressure_per_valve_set
+            .entry(opened_valve_set)
+            .or_insert(0);
+        *best_pressure = (*best_pressure).max(released_pressure);
+
+        for next_index in 1..self.flow_rates.len() {
+            if opened_valve_set & (1 << next_index) != 0 {
+                continue;
+            }
+
+            // One more minute to open the valve once we get there. It's only worth it if it opens with time to spare
+            let minutes_to_open = self.distances[current_index][next_index] + 1;
+            if minutes_to_open >= minutes_left {
+                continue;
+            }
+
+            let next_minutes_left = minutes_left - minutes_to_open;
+            self.search(
+                next_index,
+                next_minutes_left,
+                opened_valve_set | (1 << next_index),
+                // Opened valves keep releasing pressure until time runs out, so count all of it now
+                released_pressure + next_minutes_left * self.flow_rates[next_index],
+                best_pressure_per_valve_set,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valve_reads_one_or_many_tunnels() {
+        assert_eq!(
+            parse_valve("Valve BB has flow rate=13; tunnels lead to valves CC, AA"),
+            Valve {
+                name: "BB",
+                flow_rate: 13,
+                tunnels: vec!["CC", "AA"],
+            }
+        );
+        assert_eq!(
+            parse_valve("Valve HH has flow rate=22; tunnel leads to valve GG").tunnels,
+            vec!["GG"]
+        );
+    }
+
+    #[test]
+    fn network_keeps_start_and_working_valves() {
+        let input_string = std::fs::read_to_string("example.txt").unwrap();
+        let valves: Vec<Valve> = input_string.lines().map(parse_valve).collect();
+        let valve_network = ValveNetwork::new(&valves);
+
+        // AA, then BB, CC, DD, EE, HH, JJ in scan order
+        assert_eq!(valve_network.flow_rates, vec![0, 13, 2, 20, 3, 22, 21]);
+        // AA -> HH goes through DD, EE, FF and GG
+        assert_eq!(valve_network.distances[0][5], 5);
+        assert_eq!(valve_network.distances[5][0], 5);
+        // AA -> JJ goes through II
+        assert_eq!(valve_network.distances[0][6], 2);
+    }
+}
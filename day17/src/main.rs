@@ -0,0 +1,143 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::time::Instant;
+
+mod utils;
+use utils::{get_tower_height_after, parse_jets, Chamber};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+/// How many rows from the top of the tower to draw with --render
+const RENDER_ROW_COUNT: usize = 30;
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (part1_height, part2_height) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | How many units tall will the tower of rocks be after 2022 rocks have stopped falling?\nAnswer: {}",
+        part1_height
+    );
+    println!(
+        "Part 2 | How tall will the tower be after 1000000000000 rocks have stopped?\nAnswer: {}",
+        part2_height
+    );
+
+    // Draw the top of the tower after 2022 rocks
+    if std::env::args().any(|arg| arg == "--render") {
+        println!();
+        for chamber_line in get_part1_chamber(&input::read_input(INPUT_FILENAME), RENDER_ROW_COUNT)
+        {
+            println!("{}", chamber_line);
+        }
+    }
+}
+
+/// Find the tower height after 2022 rocks, and after a trillion rocks
+fn solve(input_file_name: &str) -> (u64, u64) {
+    let input_string = input::read_input(input_file_name);
+    trace!("Jet pattern => {}", &*input_string);
+
+    let jets = parse_jets(&input_string);
+    debug!("Jet pattern is {} jets long", jets.len());
+
+    (
+        get_tower_height_after(&jets, 2022),
+        get_tower_height_after(&jets, 1_000_000_000_000),
+    )
+}
+
+/// Drop 2022 rocks one by one, and draw the top rows of the chamber
+fn get_part1_chamber(input_string: &str, row_count: usize) -> Vec<String> {
+    let jets = parse_jets(input_string);
+    let mut chamber = Chamber::new(&jets);
+    while chamber.rock_count < 2022 {
+        chamber.drop_rock();
+    }
+
+    chamber.render_top(row_count)
+}
+
+/// Print the answers, the time taken to compute each of them, and the top of the tower after 2022 rocks
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let input_string = input::read_input(input_file_name);
+    let jets = parse_jets(&input_string);
+
+    let part1_start = Instant::now();
+    let part1_height = get_tower_height_after(&jets, 2022);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let part2_height = get_tower_height_after(&jets, 1_000_000_000_000);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", part1_height);
+    println!("answer\tPart 2\t{}", part2_height);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    // Rock is already drawn as '#'. Drop the walls, and the floor if it shows
+    println!("grid\tTop of the tower after 2022 rocks");
+    for chamber_line in get_part1_chamber(&input_string, RENDER_ROW_COUNT) {
+        if let Some(row_str) = chamber_line
+            .strip_prefix('|')
+            .and_then(|line_rest| line_rest.strip_suffix('|'))
+        {
+            println!("{}", row_str);
+        }
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (3068, 1514285714288));
+    }
+
+    #[test]
+    fn example_first_rocks() {
+        let jets = parse_jets(&input::read_input("example.txt"));
+        let mut chamber = Chamber::new(&jets);
+        for _ in 0..10 {
+            chamber.drop_rock();
+        }
+
+        // Same as the puzzle text's picture after the tenth rock comes to rest
+        assert_eq!(
+            chamber.render_top(20),
+            vec![
+                "|....#..|",
+                "|....#..|",
+                "|....##.|",
+                "|##..##.|",
+                "|######.|",
+                "|.###...|",
+                "|..#....|",
+                "|.####..|",
+                "|....##.|",
+                "|....##.|",
+                "|....#..|",
+                "|..#.#..|",
+                "|..#.#..|",
+                "|#####..|",
+                "|..###..|",
+                "|...#...|",
+                "|..####.|",
+                "+-------+",
+            ]
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use log::{debug, trace};
+
+/// The chamber is exactly seven units wide. Each row is stored as a bit mask, with the
+/// leftmost column in bit 6 and the rightmost column in bit 0
+pub const CHAMBER_WIDTH: usize = 7;
+const LEFT_WALL_BIT: u8 = 1 << (CHAMBER_WIDTH - 1);
+const RIGHT_WALL_BIT: u8 = 1;
+const FULL_ROW: u8 = (1 << CHAMBER_WIDTH) - 1;
+
+/// The rock shapes, in the order they fall. Rows are listed bottom first,
+/// already shifted so the left edge is two units away from the left wall
+const ROCK_SHAPES: [&[u8]; 5] = [
+    // ####
+    &[0b0011110],
+    // .#.
+    // ###
+    // .#.
+    &[0b0001000, 0b0011100, 0b0001000],
+    // ..#
+    // ..#
+    // ###
+    &[0b0011100, 0b0000100, 0b0000100],
+    // #
+    // #
+    // #
+    // #
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    // ##
+    // ##
+    &[0b0011000, 0b0011000],
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JetDirection {
+    Left,
+    Right,
+}
+
+/// Read the jet pattern the way day6 reads its datastream, one character at a time.
+/// Whitespace, like the trailing newline, is skipped
+pub fn parse_jets(input_string: &str) -> Vec<JetDirection> {
+    input_string
+        .chars()
+        .filter(|jet_char| !jet_char.is_whitespace())
+        .map(|jet_char| match jet_char {
+            '<' => JetDirection::Left,
+            '>' => JetDirection::Right,
+            strange_value => panic!("Could not match jet direction! Found: {}", strange_value),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Chamber<'a> {
+    /// Settled rock, bottom row first. Empty rows are never stored, so the length is the tower height
+    rows: Vec<u8>,
+    jets: &'a [JetDirection],
+    jet_index: usize,
+    pub rock_count: usize,
+}
+
+impl<'a> Chamber<'a> {
+    pub fn new(jets: &'a [JetDirection]) -> Chamber<'a> {
+        assert!(!jets.is_empty(), "Jet pattern is empty");
+
+        Chamber {
+            rows: Vec::new(),
+            jets,
+            jet_index: 0,
+            rock_count: 0,
+        }
+    }
+
+    pub fn get_tower_height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Does the rock overlap settled rock if its bottom row is at this height?
+    fn is_blocked(&self, rock_rows: &[u8], bottom_y: usize) -> bool {
+        rock_rows.iter().enumerate().any(|(row_offset, &rock_row)| {
+            match self.rows.get(bottom_y + row_offset) {
+                Some(&chamber_row) => chamber_row & rock_row != 0,
+                None => false,
+            }
+        })
+    }
+
+    /// Drop the next rock, and let it fall until it comes to rest
+    pub fn drop_rock(&mut self) {
+        let mut rock_rows: Vec<u8> = ROCK_SHAPES[self.rock_count % ROCK_SHAPES.len()].to_vec();
+        // The bottom edge starts three units above the highest rock, or the floor
+        let mut bottom_y = self.rows.len() + 3;
+
+        loop {
+            // Pushed by a jet of hot gas, unless a wall or settled rock is in the way
+            let jet_direction = self.jets[self.jet_index];
+            self.jet_index = (self.jet_index + 1) % self.jets.len();
+
+            let pushed_rows: Option<Vec<u8>> = match jet_direction {
+                JetDirection::Left if rock_rows.iter().all(|row| row & LEFT_WALL_BIT == 0) => {
+                    Some(rock_rows.iter().map(|row| row << 1).collect())
+                }
+                JetDirection::Right if rock_rows.iter().all(|row| row & RIGHT_WALL_BIT == 0) => {
+                    Some(rock_rows.iter().map(|row| row >> 1).collect())
+                }
+                _ => None,
+            };
+            if let Some(pushed_rows) = pushed_rows {
+                if !self.is_blocked(&pushed_rows, bottom_y) {
+                    rock_rows = pushed_rows;
+                }
+            }
+
+            // Then falls one unit, unless it would hit the floor or settled rock
+            if bottom_y == 0 || self.is_blocked(&rock_rows, bottom_y - 1) {
+                break;
+            }
+            bottom_y -= 1;
+        }
+
+        for (row_offset, rock_row) in rock_rows.into_iter().enumerate() {
+            let row_y = bottom_y + row_offset;
+            if row_y == self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[row_y] |= rock_row;
+        }
+
+        self.rock_count += 1;
+        trace!(
+            "Rock {} came to rest at height {}. Tower height is {}",
+            self.rock_count,
+            bottom_y,
+            self.rows.len()
+        );
+    }
+
+    /// Everything that decides how future rocks fall. If this repeats, so does everything after it
+    fn get_state(&self) -> (usize, usize, Vec<u8>) {
+        (
+            self.rock_count % ROCK_SHAPES.len(),
+            self.jet_index,
+            self.get_reachable_surface(),
+        )
+    }
+
+    /// The empty space a falling rock could still get into, as a bit mask per row, from the top row down.
+    /// Rocks only ever move down or sideways, so it's flooded row by row from the open air above the tower.
+    /// Settled rock, and air sealed off under it, can never be touched again, so rows below the last
+    /// reachable one make no difference to how rocks fall, however deep the reachable pockets go
+    fn get_reachable_surface(&self) -> Vec<u8> {
+        let mut reachable_rows: Vec<u8> = Vec::new();
+        // All of the open air above the tower can be reached
+        let mut reachable_above = FULL_ROW;
+
+        for &chamber_row in self.rows.iter().rev() {
+            let free_cells = !chamber_row & FULL_ROW;
+            // Straight down from the row above, then sideways along the row until settled rock is in the way
+            let mut reachable_cells = reachable_above & free_cells;
+            loop {
+                let spread_cells =
+                    (reachable_cells | (reachable_cells << 1) | (reachable_cells >> 1))
+                        & free_cells;
+                if spread_cells == reachable_cells {
+                    break;
+                }
+                reachable_cells = spread_cells;
+            }
+
+            if reachable_cells == 0 {
+                break;
+            }
+            reachable_rows.push(reachable_cells);
+            reachable_above = reachable_cells;
+        }
+
+        reachable_rows
+    }
+
+    /// Draw the top rows of the chamber the way the puzzle text does, highest row first.
+    /// The floor is drawn if the tower is short enough for it to show
+    pub fn render_top(&self, row_count: usize) -> Vec<String> {
+        let mut chamber_lines: Vec<String> = self
+            .rows
+            .iter()
+            .rev()
+            .take(row_count)
+            .map(|&chamber_row| {
+                let row_str: String = (0..CHAMBER_WIDTH)
+                    .map(|column_index| {
+                        if chamber_row & (LEFT_WALL_BIT >> column_index) != 0 {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                format!("|{}|", row_str)
+            })
+            .collect();
+
+        if self.rows.len() <= row_count {
+            chamber_lines.push(format!("+{}+", "-".repeat(CHAMBER_WIDTH)));
+        }
+
+        chamber_lines
+    }
+}
+
+/// Height of the tower after the given number of rocks have fallen.
+/// Rather than dropping every rock, watch for the chamber returning to a state it was in before.
+/// From then on, each cycle of rocks adds the same height, so whole cycles can be skipped
+pub fn get_tower_height_after(jets: &[JetDirection], total_rock_count: u64) -> u64 {
+    let mut chamber = Chamber::new(jets);
+    // State -> (rock count, tower height) when it was first seen
+    let mut seen_states: HashMap<(usize, usize, Vec<u8>), (u64, u64)> = HashMap::new();
+    // Height added by the cycles that were skipped. None until a cycle is found
+    let mut skipped_height: Option<u64> = None;
+
+    let mut rock_count: u64 = 0;
+    while rock_count < total_rock_count {
+        chamber.drop_rock();
+        rock_count += 1;
+
+        // Only look for a cycle until one is found
+        if skipped_height.is_some() {
+            continue;
+        }
+
+        let tower_height = chamber.get_tower_height() as u64;
+        let chamber_state = chamber.get_state();
+
+        if let Some(&(cycle_start_rock_count, cycle_start_height)) = seen_states.get(&chamber_state)
+        {
+            let cycle_rock_count = rock_count - cycle_start_rock_count;
+            let cycle_height = tower_height - cycle_start_height;
+            let skipped_cycles = (total_rock_count - rock_count) / cycle_rock_count;
+
+            debug!(
+                "Found a cycle of {} rocks adding {} height, starting after rock {}. Skipping {} cycles",
+                cycle_rock_count, cycle_height, cycle_start_rock_count, skipped_cycles
+            );
+
+            // The rocks left over after the skipped cycles are dropped as usual
+            rock_count += skipped_cycles * cycle_rock_count;
+            skipped_height = Some(skipped_cycles * cycle_height);
+        } else {
+            seen_states.insert(chamber_state, (rock_count, tower_height));
+        }
+    }
+
+    chamber.get_tower_height() as u64 + skipped_height.unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_jets_skips_trailing_newline() {
+        assert_eq!(
+            parse_jets("<>>\n"),
+            vec![JetDirection::Left, JetDirection::Right, JetDirection::Right]
+        );
+    }
+
+    #[test]
+    fn rocks_stop_at_walls_and_floor() {
+        // Always pushed right. The flat rock slides against the right wall and lands on the floor
+        let jets = [JetDirection::Right];
+        let mut chamber = Chamber::new(&jets);
+        chamber.drop_rock();
+
+        assert_eq!(chamber.get_tower_height(), 1);
+        assert_eq!(chamber.render_top(1), vec!["|...####|", "+-------+"]);
+    }
+
+    #[test]
+    fn reachable_surface_follows_deep_pockets() {
+        let jets = [JetDirection::Right];
+        let mut chamber = Chamber::new(&jets);
+
+        // Only the rightmost column is open, all the way down to the floor. Every row of it matters,
+        // as a vertical rock could fall all the way down
+        chamber.rows = vec![0b1111110; 40];
+        assert_eq!(chamber.get_reachable_surface(), vec![0b0000001; 40]);
+
+        // The same pocket, with its top closed off. Nothing under the full row can be reached
+        chamber.rows.push(0b1111111);
+        assert_eq!(chamber.get_reachable_surface(), vec![]);
+
+        // The open columns on the right are reached from above. The three open cells in the middle of
+        // the lower row only have settled rock above and beside them, so they're sealed off
+        chamber.rows = vec![0b1100010, 0b1111100];
+        assert_eq!(chamber.get_reachable_surface(), vec![0b0000011, 0b0000001]);
+    }
+
+    #[test]
+    fn cycle_skipping_matches_dropping_every_rock() {
+        let jets = parse_jets(&std::fs::read_to_string("example.txt").unwrap());
+
+        for total_rock_count in [1, 5, 100, 1000, 2022] {
+            let mut chamber = Chamber::new(&jets);
+            for _ in 0..total_rock_count {
+                chamber.drop_rock();
+            }
+
+            assert_eq!(
+                get_tower_height_after(&jets, total_rock_count as u64),
+                chamber.get_tower_height() as u64
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::collections::{HashSet, VecDeque};
+use std::time::Instant;
+
+const INPUT_FILENAME: &str = "example.txt";
+
+/// The six unit steps to a face-adjacent cube. Points are [x, y, z], the 3D version of day9's [x, y]
+const NEIGHBOUR_OFFSETS: [[i32; 3]; 6] = [
+    [1, 0, 0],
+    [-1, 0, 0],
+    [0, 1, 0],
+    [0, -1, 0],
+    [0, 0, 1],
+    [0, 0, -1],
+];
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (surface_area, exterior_surface_area) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the surface area of your scanned lava droplet?\nAnswer: {}",
+        surface_area
+    );
+    println!(
+        "Part 2 | What is the exterior surface area of your scanned lava droplet?\nAnswer: {}",
+        exterior_surface_area
+    );
+}
+
+fn solve(input_file_name: &str) -> (usize, usize) {
+    let lava_cubes = parse_cubes(&input::read_input(input_file_name));
+    debug!("Scanned {} lava cubes", lava_cubes.len());
+
+    (
+        get_surface_area(&lava_cubes),
+        get_exterior_surface_area(&lava_cubes),
+    )
+}
+
+/// Every line is one cube. Example: "2,2,2"
+fn parse_cubes(input_string: &str) -> HashSet<[i32; 3]> {
+    input_string
+        .lines()
+        .map(|input_line| input_line.trim())
+        .filter(|input_line| !input_line.is_empty())
+        .map(|input_line| {
+            let coordinates: Vec<i32> = input_line
+                .split(',')
+                .map(|coordinate_str| {
+                    coordinate_str
+                        .parse::<i32>()
+                        .expect("Could not parse cube coordinate")
+                })
+                .collect();
+
+            coordinates
+                .try_into()
+                .unwrap_or_else(|_| panic!("Cube {} does not have three coordinates", input_line))
+        })
+        .collect()
+}
+
+fn get_neighbours(cube: [i32; 3]) -> impl Iterator<Item = [i32; 3]> {
+    NEIGHBOUR_OFFSETS.iter().map(move |offset| {
+        [
+            cube[0] + offset[0],
+            cube[1] + offset[1],
+            cube[2] + offset[2],
+        ]
+    })
+}
+
+/// Part 1 - Count the faces that aren't touching another lava cube, including those facing air pockets
+fn get_surface_area(lava_cubes: &HashSet<[i32; 3]>) -> usize {
+    lava_cubes
+        .iter()
+        .flat_map(|&lava_cube| get_neighbours(lava_cube))
+        .filter(|neighbour_cube| !lava_cubes.contains(neighbour_cube))
+        .count()
+}
+
+/// Part 2 - Count only the faces that steam can reach from outside the droplet.
+/// Flood fill the air in a box one unit bigger than the droplet on every side. The air around the
+/// outside is all connected that way, and every lava face the fill bumps into is an exterior face
+fn get_exterior_surface_area(lava_cubes: &HashSet<[i32; 3]>) -> usize {
+    get_exterior_faces(lava_cubes).len()
+}
+
+/// Every exterior face, as the (lava cube, air cube) pair it sits between
+fn get_exterior_faces(lava_cubes: &HashSet<[i32; 3]>) -> Vec<([i32; 3], [i32; 3])> {
+    if lava_cubes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut min_corner = [i32::MAX; 3];
+    let mut max_corner = [i32::MIN; 3];
+    for lava_cube in lava_cubes {
+        for axis in 0..3 {
+            min_corner[axis] = min_corner[axis].min(lava_cube[axis] - 1);
+            max_corner[axis] = max_corner[axis].max(lava_cube[axis] + 1);
+        }
+    }
+    trace!("Flood fill bounds: {:?} to {:?}", min_corner, max_corner);
+
+    let is_in_bounds = |cube: &[i32; 3]| {
+        (0..3).all(|axis| (min_corner[axis]..=max_corner[axis]).contains(&cube[axis]))
+    };
+
+    let mut exterior_faces: Vec<([i32; 3], [i32; 3])> = Vec::new();
+    // One corner of the box is always outside the droplet
+    let mut visited_air: HashSet<[i32; 3]> = HashSet::from([min_corner]);
+    let mut fill_queue: VecDeque<[i32; 3]> = VecDeque::from([min_corner]);
+
+    while let Some(air_cube) = fill_queue.pop_front() {
+        for neighbour_cube in get_neighbours(air_cube) {
+            if lava_cubes.contains(&neighbour_cube) {
+                exterior_faces.push((neighbour_cube, air_cube));
+                continue;
+            }
+
+            if is_in_bounds(&neighbour_cube) && visited_air.insert(neighbour_cube) {
+                fill_queue.push_back(neighbour_cube);
+            }
+        }
+    }
+
+    debug!("Flood fill reached {} air cubes", visited_air.len());
+    exterior_faces
+}
+
+/// Print the answers, the time taken to compute each of them, and the exterior faces on each z layer
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let lava_cubes = parse_cubes(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let surface_area = get_surface_area(&lava_cubes);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let exterior_surface_area = get_exterior_surface_area(&lava_cubes);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", surface_area);
+    println!("answer\tPart 2\t{}", exterior_surface_area);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    let exterior_faces = get_exterior_faces(&lava_cubes);
+    let min_z = lava_cubes.iter().map(|cube| cube[2]).min().unwrap_or(0);
+    let max_z = lava_cubes.iter().map(|cube| cube[2]).max().unwrap_or(0);
+
+    println!("bars\tExterior faces of the lava cubes on each z layer");
+    for z in min_z..=max_z {
+        let layer_face_count = exterior_faces
+            .iter()
+            .filter(|(lava_cube, _)| lava_cube[2] == z)
+            .count();
+        println!("0\tz = {}\t{}", z, layer_face_count);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (64, 58));
+    }
+
+    #[test]
+    fn two_adjacent_cubes() {
+        let lava_cubes = parse_cubes("1,1,1\n2,1,1\n");
+
+        assert_eq!(get_surface_area(&lava_cubes), 10);
+        assert_eq!(get_exterior_surface_area(&lava_cubes), 10);
+    }
+
+    #[test]
+    fn hollow_cube_has_no_exterior_faces_inside() {
+        // A 3x3x3 block with the middle cube missing
+        let mut lava_cubes: HashSet<[i32; 3]> = HashSet::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                for z in 0..3 {
+                    if [x, y, z] != [1, 1, 1] {
+                        lava_cubes.insert([x, y, z]);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(get_surface_area(&lava_cubes), 54 + 6);
+        assert_eq!(get_exterior_surface_area(&lava_cubes), 54);
+    }
+}
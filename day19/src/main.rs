@@ -0,0 +1,160 @@
+use aoc_common::input;
+use log::debug;
+use std::thread;
+use std::time::Instant;
+
+mod utils;
+use utils::Blueprint;
+
+const INPUT_FILENAME: &str = "example.txt";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (quality_levels, first_three_product) = solve(INPUT_FILENAME);
+
+    for (blueprint_id, quality_level) in &quality_levels {
+        println!(
+            "Blueprint {} quality level: {}",
+            blueprint_id, quality_level
+        );
+    }
+
+    println!(
+        "Part 1 | What do you get if you add up the quality level of all of the blueprints in your list?\nAnswer: {}",
+        get_quality_level_sum(&quality_levels)
+    );
+    println!(
+        "Part 2 | What do you get if you multiply the largest number of geodes you could open with the first three blueprints in 32 minutes?\nAnswer: {}",
+        first_three_product
+    );
+}
+
+/// Return the (Blueprint id, Quality level) of every blueprint, in input order, and the part 2 geode product
+fn solve(input_file_name: &str) -> (Vec<(u32, u32)>, u32) {
+    let blueprints = parse_blueprints(&input::read_input(input_file_name));
+    debug!("Parsed {} blueprints", blueprints.len());
+
+    let max_geodes_per_blueprint = get_max_geodes_per_blueprint(&blueprints, 24);
+
+    (
+        get_quality_levels(&blueprints, &max_geodes_per_blueprint),
+        get_first_three_product(&blueprints),
+    )
+}
+
+fn parse_blueprints(input_string: &str) -> Vec<Blueprint> {
+    input_string
+        .lines()
+        .filter(|input_line| !input_line.trim().is_empty())
+        .map(Blueprint::new)
+        .collect()
+}
+
+/// Blueprints don't depend on each other, so each one is searched on its own thread
+fn get_max_geodes_per_blueprint(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    thread::scope(|thread_scope| {
+        let search_handles: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| thread_scope.spawn(move || blueprint.get_max_geodes(minutes)))
+            .collect();
+
+        search_handles
+            .into_iter()
+            .map(|search_handle| search_handle.join().expect("Blueprint search panicked"))
+            .collect()
+    })
+}
+
+/// Part 1 - A blueprint's quality level is its id times the most geodes it can open in 24 minutes.
+/// The answer is the sum of all of them. Each quality level is kept with its blueprint's id
+fn get_quality_levels(
+    blueprints: &[Blueprint],
+    max_geodes_per_blueprint: &[u32],
+) -> Vec<(u32, u32)> {
+    blueprints
+        .iter()
+        .zip(max_geodes_per_blueprint)
+        .map(|(blueprint, max_geodes)| (blueprint.id, blueprint.id * max_geodes))
+        .collect()
+}
+
+fn get_quality_level_sum(quality_levels: &[(u32, u32)]) -> u32 {
+    quality_levels
+        .iter()
+        .map(|(_, quality_level)| quality_level)
+        .sum()
+}
+
+/// Part 2 - Only the first three blueprints survived the elephants, but now there are 32 minutes
+fn get_first_three_product(blueprints: &[Blueprint]) -> u32 {
+    let first_blueprints = &blueprints[..blueprints.len().min(3)];
+
+    get_max_geodes_per_blueprint(first_blueprints, 32)
+        .into_iter()
+        .product()
+}
+
+/// Print the answers, the time taken to compute each of them, and each blueprint's quality level
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let blueprints = parse_blueprints(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let max_geodes_per_blueprint = get_max_geodes_per_blueprint(&blueprints, 24);
+    let quality_levels = get_quality_levels(&blueprints, &max_geodes_per_blueprint);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let first_three_product = get_first_three_product(&blueprints);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", get_quality_level_sum(&quality_levels));
+    println!("answer\tPart 2\t{}", first_three_product);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    println!("bars\tQuality level of each blueprint");
+    for (blueprint_id, quality_level) in quality_levels {
+        println!("0\tBlueprint {}\t{}", blueprint_id, quality_level);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (vec![(1, 9), (2, 24)], 56 * 62));
+    }
+
+    #[test]
+    fn example_max_geodes() {
+        let blueprints = parse_blueprints(&input::read_input("example.txt"));
+
+        assert_eq!(get_max_geodes_per_blueprint(&blueprints, 24), vec![9, 12]);
+        assert_eq!(get_max_geodes_per_blueprint(&blueprints, 32), vec![56, 62]);
+    }
+
+    #[test]
+    fn quality_levels_keep_blueprint_ids() {
+        // The example's blueprints, renumbered out of order
+        let input_string = input::read_input("example.txt")
+            .replace("Blueprint 1:", "Blueprint 7:")
+            .replace("Blueprint 2:", "Blueprint 3:");
+        let blueprints = parse_blueprints(&input_string);
+
+        let quality_levels = get_quality_levels(&blueprints, &[9, 12]);
+        assert_eq!(quality_levels, vec![(7, 63), (3, 36)]);
+        assert_eq!(get_quality_level_sum(&quality_levels), 99);
+    }
+}
@@ -0,0 +1,191 @@
+use log::trace;
+
+/// Resources, and the robots that collect them, are indexed in this order
+pub const ORE: usize = 0;
+pub const CLAY: usize = 1;
+pub const OBSIDIAN: usize = 2;
+pub const GEODE: usize = 3;
+const RESOURCE_NAMES: [&str; 4] = ["ore", "clay", "obsidian", "geode"];
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Blueprint {
+    pub id: u32,
+    /// robot_costs[robot][resource] is how much of the resource it takes to build that kind of robot
+    pub robot_costs: [[u32; 4]; 4],
+}
+
+impl Blueprint {
+    /// Example: "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore.
+    /// Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian."
+    pub fn new(input_line: &str) -> Blueprint {
+        let (id_str, robots_str) = input_line
+            .trim()
+            .strip_prefix("Blueprint ")
+            .and_then(|line_rest| line_rest.split_once(':'))
+            .expect("Could not read blueprint id");
+
+        let mut robot_costs = [[0; 4]; 4];
+
+        for robot_str in robots_str
+            .split('.')
+            .map(str::trim)
+            .filter(|robot_str| !robot_str.is_empty())
+        {
+            // "Each obsidian robot costs 3 ore and 14 clay"
+            let (robot_name, costs_str) = robot_str
+                .strip_prefix("Each ")
+                .and_then(|robot_rest| robot_rest.split_once(" robot costs "))
+                .expect("Could not read robot type and costs");
+
+            let robot = get_resource_index(robot_name);
+            for cost_str in costs_str.split(" and ") {
+                let (amount_str, resource_name) = cost_str
+                    .split_once(' ')
+                    .expect("Could not split cost into amount and resource");
+                robot_costs[robot][get_resource_index(resource_name)] = amount_str
+                    .parse::<u32>()
+                    .expect("Could not parse cost amount");
+            }
+        }
+
+        Blueprint {
+            id: id_str.parse::<u32>().expect("Could not parse blueprint id"),
+            robot_costs,
+        }
+    }
+
+    /// The most geodes that can be opened in the time given, starting with a single ore robot
+    pub fn get_max_geodes(&self, minutes: u32) -> u32 {
+        // We can only build one robot per minute, so there's no point collecting more of a resource per minute
+        // than the most any robot costs. Geode robots are the exception, we want as many as we can get
+        let mut max_useful_robots = [u32::MAX; 4];
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            max_useful_robots[resource] = self
+                .robot_costs
+                .iter()
+                .map(|costs| costs[resource])
+                .max()
+                .unwrap_or(0);
+        }
+
+        let mut max_geodes = 0;
+        self.search(
+            &SearchState {
+                minutes_left: minutes,
+                robots: [1, 0, 0, 0],
+                resources: [0; 4],
+            },
+            &max_useful_robots,
+            &mut max_geodes,
+        );
+
+        trace!("Blueprint {} opens at most {} geodes", self.id, max_geodes);
+        max_geodes
+    }
+
+    /// Branch and bound over which robot to build next. Rather than stepping one minute at a time,
+    /// each branch waits until the robot can be afforded and then builds it.
+    /// Once a robot can't be built before time runs out, the branch just lets the robots keep collecting
+    fn search(&self, state: &SearchState, max_useful_robots: &[u32; 4], max_geodes: &mut u32) {
+        // Geodes opened if no more robots are built
+        let idle_geodes = state.resources[GEODE] + state.robots[GEODE] * state.minutes_left;
+        *max_geodes = (*max_geodes).max(idle_geodes);
+
+        // Even building a geode robot every remaining minute can't beat the best so far
+        let optimistic_geodes =
+            idle_geodes + state.minutes_left * state.minutes_left.saturating_sub(1) / 2;
+        if optimistic_geodes <= *max_geodes {
+            return;
+        }
+
+        // Geode robots first, as they tend to find good answers early and so prune more
+        for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+            if state.robots[robot] >= max_useful_robots[robot] {
+                continue;
+            }
+
+            if let Some(next_state) = self.build_robot(state, robot) {
+                self.search(&next_state, max_useful_robots, max_geodes);
+            }
+        }
+    }
+
+    /// Wait for enough resources, then spend a minute building the robot.
+    /// None if we don't collect what it needs, or it wouldn't be ready before time runs out
+    fn build_robot(&self, state: &SearchState, robot: usize) -> Option<SearchState> {
+        let robot_costs = &self.robot_costs[robot];
+
+        let mut wait_minutes = 0;
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            let missing_amount = robot_costs[resource].saturating_sub(state.resources[resource]);
+            if missing_amount == 0 {
+                continue;
+            }
+            if state.robots[resource] == 0 {
+                return None;
+            }
+            wait_minutes = wait_minutes.max(missing_amount.div_ceil(state.robots[resource]));
+        }
+
+        // A robot finished with no minutes left can't collect anything
+        let elapsed_minutes = wait_minutes + 1;
+        if elapsed_minutes >= state.minutes_left {
+            return None;
+        }
+
+        let mut next_state = SearchState {
+            minutes_left: state.minutes_left - elapsed_minutes,
+            robots: state.robots,
+            resources: [0; 4],
+        };
+        for resource in [ORE, CLAY, OBSIDIAN, GEODE] {
+            next_state.resources[resource] = state.resources[resource]
+                + state.robots[resource] * elapsed_minutes
+                - robot_costs[resource];
+        }
+        next_state.robots[robot] += 1;
+
+        Some(next_state)
+    }
+}
+
+#[derive(Debug)]
+struct SearchState {
+    minutes_left: u32,
+    robots: [u32; 4],
+    resources: [u32; 4],
+}
+
+fn get_resource_index(resource_name: &str) -> usize {
+    RESOURCE_NAMES
+        .iter()
+        .position(|&name| name == resource_name)
+        .unwrap_or_else(|| panic!("Could not match resource! Found: {}", resource_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blueprint_reads_every_robot_cost() {
+        let blueprint = Blueprint::new(
+            "Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.",
+        );
+
+        assert_eq!(blueprint.id, 2);
+        assert_eq!(
+            blueprint.robot_costs,
+            [[2, 0, 0, 0], [3, 0, 0, 0], [3, 8, 0, 0], [3, 0, 12, 0]]
+        );
+    }
+
+    #[test]
+    fn nothing_to_open_without_enough_time() {
+        let blueprint = Blueprint::new(
+            "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.",
+        );
+
+        assert_eq!(blueprint.get_max_geodes(10), 0);
+    }
+}
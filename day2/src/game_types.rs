@@ -14,9 +14,9 @@ pub enum GameResult {
 impl PointSystem for GameResult {
     fn get_points(&self) -> i32 {
         match self {
-            Self::Win => return 6,
-            Self::Loss => return 0,
-            Self::Draw => return 3,
+            Self::Win => 6,
+            Self::Loss => 0,
+            Self::Draw => 3,
         }
     }
 }
@@ -31,9 +31,9 @@ pub enum GameMove {
 impl PointSystem for GameMove {
     fn get_points(&self) -> i32 {
         match self {
-            Self::Rock => return 1,
-            Self::Paper => return 2,
-            Self::Scissors => return 3,
+            Self::Rock => 1,
+            Self::Paper => 2,
+            Self::Scissors => 3,
         }
     }
 }
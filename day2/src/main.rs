@@ -2,19 +2,36 @@ use log::{debug, trace};
 use std::{
     fs::File,
     io::{BufRead, BufReader},
+    time::Instant,
 };
 
 mod game_types;
 use game_types::{GameMove, GameResult, PointSystem};
 
+const INPUT_FILENAME: &str = "input.txt";
+
 fn main() {
     //Init logger
     //Set RUST_LOG="trace" to enable logging for all log levels.
     // In Powershell, this is `$env:RUST_LOG = "trace"`
     env_logger::init();
 
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (total_score_part1, total_score_part2) = solve(INPUT_FILENAME);
+
+    println!("Part 1 Solution aka Total Score: {}", total_score_part1);
+    println!("Part 2 Solution aka Total Score: {}", total_score_part2);
+}
+
+/// Play every round in the strategy guide, and return the total scores for both parts
+fn solve(input_file_name: &str) -> (i32, i32) {
     let input_file_lines =
-        BufReader::new(File::open("input.txt").expect("Unable to open input file!")).lines();
+        BufReader::new(File::open(input_file_name).expect("Unable to open input file!")).lines();
 
     let mut total_score_part1 = 0;
     let mut total_score_part2 = 0;
@@ -24,7 +41,7 @@ fn main() {
             debug!("--");
             trace!("Current line is {}", current_line);
 
-            let coded_moves: Vec<&str> = current_line.split(" ").collect();
+            let coded_moves: Vec<&str> = current_line.split(' ').collect();
 
             let coded_opponent_move = coded_moves[0];
             let coded_our_move = coded_moves[1];
@@ -72,8 +89,7 @@ fn main() {
         }
     }
 
-    println!("Part 1 Solution aka Total Score: {}", total_score_part1);
-    println!("Part 2 Solution aka Total Score: {}", total_score_part2);
+    (total_score_part1, total_score_part2)
 }
 
 fn interpret_move(coded_move: &str) -> Option<GameMove> {
@@ -126,17 +142,135 @@ fn get_move_for_desired_result(
     }
 
     if desired_result == GameResult::Win {
-        match opponent_move {
-            GameMove::Rock => return Some(GameMove::Paper),
-            GameMove::Paper => return Some(GameMove::Scissors),
-            GameMove::Scissors => return Some(GameMove::Rock),
-        }
+        return match opponent_move {
+            GameMove::Rock => Some(GameMove::Paper),
+            GameMove::Paper => Some(GameMove::Scissors),
+            GameMove::Scissors => Some(GameMove::Rock),
+        };
     }
 
     // if desired_result == GameResult::Loss {
     match opponent_move {
-        GameMove::Rock => return Some(GameMove::Scissors),
-        GameMove::Paper => return Some(GameMove::Rock),
-        GameMove::Scissors => return Some(GameMove::Paper),
+        GameMove::Rock => Some(GameMove::Scissors),
+        GameMove::Paper => Some(GameMove::Rock),
+        GameMove::Scissors => Some(GameMove::Paper),
+    }
+}
+
+/// Print the answers, and the time taken to compute them, in the format read by the report crate.
+/// Both parts are computed in the same pass, so they share a single timing
+fn print_report(input_file_name: &str) {
+    let solve_start = Instant::now();
+    let (part1_answer, part2_answer) = solve(input_file_name);
+    let solve_time = solve_start.elapsed();
+
+    println!("answer\tPart 1\t{}", part1_answer);
+    println!("answer\tPart 2\t{}", part2_answer);
+    println!("timing\tBoth parts\t{}", solve_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (15, 12));
+    }
+
+    #[test]
+    fn decide_game_result_covers_every_pairing() {
+        use GameMove::{Paper, Rock, Scissors};
+
+        assert_eq!(decide_game_result(Rock, Rock), GameResult::Draw);
+        assert_eq!(decide_game_result(Paper, Paper), GameResult::Draw);
+        assert_eq!(decide_game_result(Scissors, Scissors), GameResult::Draw);
+
+        assert_eq!(decide_game_result(Rock, Scissors), GameResult::Win);
+        assert_eq!(decide_game_result(Paper, Rock), GameResult::Win);
+        assert_eq!(decide_game_result(Scissors, Paper), GameResult::Win);
+
+        assert_eq!(decide_game_result(Rock, Paper), GameResult::Loss);
+        assert_eq!(decide_game_result(Paper, Scissors), GameResult::Loss);
+        assert_eq!(decide_game_result(Scissors, Rock), GameResult::Loss);
+    }
+
+    #[test]
+    fn get_move_for_desired_result_matches_example_rounds() {
+        // "A Y" - Opponent plays Rock, we need a draw
+        assert_eq!(
+            get_move_for_desired_result(GameMove::Rock, GameResult::Draw),
+            Some(GameMove::Rock)
+        );
+        // "B X" - Opponent plays Paper, we need to lose
+        assert_eq!(
+            get_move_for_desired_result(GameMove::Paper, GameResult::Loss),
+            Some(GameMove::Rock)
+        );
+        // "C Z" - Opponent plays Scissors, we need to win
+        assert_eq!(
+            get_move_for_desired_result(GameMove::Scissors, GameResult::Win),
+            Some(GameMove::Rock)
+        );
+    }
+
+    #[test]
+    fn interpret_move_rejects_unknown_codes() {
+        assert_eq!(interpret_move("A"), Some(GameMove::Rock));
+        assert_eq!(interpret_move("Y"), Some(GameMove::Paper));
+        assert_eq!(interpret_move("Z"), Some(GameMove::Scissors));
+        assert_eq!(interpret_move("D"), None);
+        assert_eq!(interpret_coded_desired_result("A"), None);
+    }
+
+    fn any_game_move() -> impl Strategy<Value = GameMove> {
+        prop_oneof![
+            Just(GameMove::Rock),
+            Just(GameMove::Paper),
+            Just(GameMove::Scissors)
+        ]
+    }
+
+    fn any_game_result() -> impl Strategy<Value = GameResult> {
+        prop_oneof![
+            Just(GameResult::Win),
+            Just(GameResult::Loss),
+            Just(GameResult::Draw)
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn desired_result_is_reproduced(
+            opponent_move in any_game_move(),
+            desired_result in any_game_result(),
+        ) {
+            let ideal_move = get_move_for_desired_result(opponent_move, desired_result).unwrap();
+            prop_assert_eq!(decide_game_result(ideal_move, opponent_move), desired_result);
+        }
+
+        #[test]
+        fn swapping_moves_swaps_the_result(
+            our_move in any_game_move(),
+            opponent_move in any_game_move(),
+        ) {
+            let flipped_result = match decide_game_result(our_move, opponent_move) {
+                GameResult::Win => GameResult::Loss,
+                GameResult::Loss => GameResult::Win,
+                GameResult::Draw => GameResult::Draw,
+            };
+            prop_assert_eq!(decide_game_result(opponent_move, our_move), flipped_result);
+        }
+
+        #[test]
+        fn round_score_stays_within_bounds(
+            our_move in any_game_move(),
+            opponent_move in any_game_move(),
+        ) {
+            let round_score =
+                our_move.get_points() + decide_game_result(our_move, opponent_move).get_points();
+            prop_assert!((1..=9).contains(&round_score));
+        }
     }
 }
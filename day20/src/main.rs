@@ -0,0 +1,171 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::time::Instant;
+
+mod utils;
+use utils::MixingOrder;
+
+const INPUT_FILENAME: &str = "example.txt";
+
+const DECRYPTION_KEY: i64 = 811_589_153;
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (mixed_once_sum, decrypted_sum) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the sum of the three numbers that form the grove coordinates?\nAnswer: {}",
+        mixed_once_sum
+    );
+    println!(
+        "Part 2 | Apply the decryption key and mix your encrypted list of numbers ten times. What is the sum of the three numbers that form the grove coordinates?\nAnswer: {}",
+        decrypted_sum
+    );
+}
+
+fn solve(input_file_name: &str) -> (i64, i64) {
+    let numbers = parse_numbers(&input::read_input(input_file_name));
+    debug!("Parsed {} numbers", numbers.len());
+
+    (
+        get_grove_coordinate_sum(&numbers, 1, 1),
+        get_grove_coordinate_sum(&numbers, DECRYPTION_KEY, 10),
+    )
+}
+
+fn parse_numbers(input_string: &str) -> Vec<i64> {
+    input_string
+        .lines()
+        .map(|input_line| input_line.trim())
+        .filter(|input_line| !input_line.is_empty())
+        .map(|input_line| input_line.parse::<i64>().expect("Could not parse number"))
+        .collect()
+}
+
+/// Mix the numbers, and return them in their final order, starting from wherever the list happens to start.
+/// Each number is moved by its index in the original list, as numbers can repeat. See MixingOrder
+fn mix(numbers: &[i64], decryption_key: i64, round_count: usize) -> Vec<i64> {
+    let numbers: Vec<i64> = numbers
+        .iter()
+        .map(|number| number * decryption_key)
+        .collect();
+    let mut mixing_order = MixingOrder::new(numbers.len());
+
+    // With the moving number taken out, there are only len - 1 places it can go.
+    // Moving it len - 1 places lands it right back where it started
+    let cycle_length = numbers.len() as i64 - 1;
+    if cycle_length <= 0 {
+        return numbers;
+    }
+
+    for round_number in 1..=round_count {
+        for (original_index, &number) in numbers.iter().enumerate() {
+            let current_position = mixing_order.remove(original_index);
+            let new_position = (current_position as i64 + number).rem_euclid(cycle_length) as usize;
+            mixing_order.insert(new_position, original_index);
+        }
+
+        trace!(
+            "After round {}: {:?}",
+            round_number,
+            mixing_order
+                .to_original_indexes()
+                .into_iter()
+                .map(|original_index| numbers[original_index])
+                .collect::<Vec<i64>>()
+        );
+    }
+
+    mixing_order
+        .to_original_indexes()
+        .into_iter()
+        .map(|original_index| numbers[original_index])
+        .collect()
+}
+
+/// The grove coordinates are the numbers 1000, 2000 and 3000 places after the 0, wrapping around the list
+fn get_grove_coordinate_sum(numbers: &[i64], decryption_key: i64, round_count: usize) -> i64 {
+    let mixed_numbers = mix(numbers, decryption_key, round_count);
+
+    let zero_position = mixed_numbers
+        .iter()
+        .position(|&number| number == 0)
+        .expect("List has no 0");
+
+    let grove_coordinates: Vec<i64> = [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed_numbers[(zero_position + offset) % mixed_numbers.len()])
+        .collect();
+    debug!("Grove coordinates: {:?}", grove_coordinates);
+
+    grove_coordinates.iter().sum()
+}
+
+/// Print the answers and the time taken to compute each of them in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let numbers = parse_numbers(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let mixed_once_sum = get_grove_coordinate_sum(&numbers, 1, 1);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let decrypted_sum = get_grove_coordinate_sum(&numbers, DECRYPTION_KEY, 10);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", mixed_once_sum);
+    println!("answer\tPart 2\t{}", decrypted_sum);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rotate the list so it starts at the 0. Where a circular list "starts" doesn't matter
+    fn starting_from_zero(mixed_numbers: Vec<i64>) -> Vec<i64> {
+        let zero_position = mixed_numbers
+            .iter()
+            .position(|&number| number == 0)
+            .unwrap();
+        let mut rotated_numbers = mixed_numbers;
+        rotated_numbers.rotate_left(zero_position);
+        rotated_numbers
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (3, 1623178306));
+    }
+
+    #[test]
+    fn example_mixed_once() {
+        let numbers = parse_numbers(&input::read_input("example.txt"));
+
+        assert_eq!(
+            starting_from_zero(mix(&numbers, 1, 1)),
+            vec![0, 3, -2, 1, 2, -3, 4]
+        );
+    }
+
+    #[test]
+    fn duplicates_move_independently() {
+        // Both 1s move one place right, in turn. Moves longer than len - 1 places wrap around the list
+        assert_eq!(starting_from_zero(mix(&[1, 1, 0], 1, 1)), vec![0, 1, 1]);
+        assert_eq!(starting_from_zero(mix(&[0, 4, 5], 1, 1)), vec![0, 5, 4]);
+    }
+
+    #[test]
+    fn single_zero_stays_put() {
+        assert_eq!(mix(&[0], DECRYPTION_KEY, 10), vec![0]);
+    }
+}
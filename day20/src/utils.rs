@@ -0,0 +1,130 @@
+/// The order of the numbers while mixing, as indexes into the original list, split into blocks.
+///
+/// Numbers can repeat, so they can't be found by value. Each number is known by its index in the original list,
+/// which never changes, and block_indexes says which block each of them is in. Finding a number's position
+/// only scans its own block, and adds up the lengths of the blocks before it. Moving a number only shifts
+/// the indexes inside the two blocks involved. With blocks of about sqrt(len) indexes, each move is O(sqrt(len))
+/// rather than the O(len) of searching and shifting one flat list
+#[derive(Debug, Clone)]
+pub struct MixingOrder {
+    blocks: Vec<Vec<usize>>,
+    /// The block each original index is in
+    block_indexes: Vec<usize>,
+    block_size: usize,
+}
+
+impl MixingOrder {
+    /// The numbers in their original order
+    pub fn new(number_count: usize) -> MixingOrder {
+        let mut mixing_order = MixingOrder {
+            blocks: vec![(0..number_count).collect()],
+            block_indexes: vec![0; number_count],
+            block_size: ((number_count as f64).sqrt() as usize).max(1),
+        };
+        mixing_order.rebuild_blocks();
+
+        mixing_order
+    }
+
+    /// Take the number out of the order, and return the position it was at
+    pub fn remove(&mut self, original_index: usize) -> usize {
+        let block_index = self.block_indexes[original_index];
+        let block_offset = self.blocks[block_index]
+            .iter()
+            .position(|&block_original_index| block_original_index == original_index)
+            .expect("Every original index is in the block it's recorded in");
+        self.blocks[block_index].remove(block_offset);
+
+        self.blocks[..block_index]
+            .iter()
+            .map(|block| block.len())
+            .sum::<usize>()
+            + block_offset
+    }
+
+    /// Put the number back in at the position, shifting everything from there on along by one
+    pub fn insert(&mut self, position: usize, original_index: usize) {
+        let mut block_index = 0;
+        let mut block_offset = position;
+        // A position at the end of one block goes there, rather than at the start of the next one
+        while block_index + 1 < self.blocks.len() && block_offset > self.blocks[block_index].len() {
+            block_offset -= self.blocks[block_index].len();
+            block_index += 1;
+        }
+
+        self.blocks[block_index].insert(block_offset, original_index);
+        self.block_indexes[original_index] = block_index;
+
+        // Keep shifting cheap. Blocks that have emptied out get dropped at the same time
+        if self.blocks[block_index].len() > 2 * self.block_size {
+            self.rebuild_blocks();
+        }
+    }
+
+    /// The original indexes in their current order
+    pub fn to_original_indexes(&self) -> Vec<usize> {
+        self.blocks.iter().flatten().copied().collect()
+    }
+
+    /// Split the order back into blocks of block_size
+    fn rebuild_blocks(&mut self) {
+        let original_indexes = self.to_original_indexes();
+        self.blocks = original_indexes
+            .chunks(self.block_size)
+            .map(|block| block.to_vec())
+            .collect();
+        if self.blocks.is_empty() {
+            self.blocks.push(Vec::new());
+        }
+
+        for (block_index, block) in self.blocks.iter().enumerate() {
+            for &original_index in block {
+                self.block_indexes[original_index] = block_index;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_match_a_flat_list() {
+        // Pseudo-random moves over enough numbers for blocks to fill up and be rebuilt many times
+        let number_count = 200;
+        let mut mixing_order = MixingOrder::new(number_count);
+        let mut flat_order: Vec<usize> = (0..number_count).collect();
+        let mut seed: u64 = 12345;
+
+        for _ in 0..5000 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let original_index = (seed >> 33) as usize % number_count;
+            let new_position = (seed >> 13) as usize % number_count;
+
+            let flat_position = flat_order
+                .iter()
+                .position(|&flat_original_index| flat_original_index == original_index)
+                .unwrap();
+            flat_order.remove(flat_position);
+            flat_order.insert(new_position, original_index);
+
+            assert_eq!(mixing_order.remove(original_index), flat_position);
+            mixing_order.insert(new_position, original_index);
+        }
+
+        assert_eq!(mixing_order.to_original_indexes(), flat_order);
+    }
+
+    #[test]
+    fn empty_and_single_number_orders() {
+        assert_eq!(MixingOrder::new(0).to_original_indexes(), vec![]);
+
+        let mut mixing_order = MixingOrder::new(1);
+        assert_eq!(mixing_order.remove(0), 0);
+        mixing_order.insert(0, 0);
+        assert_eq!(mixing_order.to_original_indexes(), vec![0]);
+    }
+}
@@ -0,0 +1,129 @@
+use aoc_common::input;
+use log::debug;
+use std::time::Instant;
+
+mod utils;
+use utils::{Job, MonkeyTree, SolveError};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+const ROOT_NAME: &str = "root";
+const HUMAN_NAME: &str = "humn";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (root_number, human_number) = match solve(INPUT_FILENAME) {
+        Ok(answers) => answers,
+        Err(solve_error) => {
+            eprintln!("Could not find the number to yell: {}", solve_error);
+            std::process::exit(1);
+        }
+    };
+
+    println!(
+        "Part 1 | What number will the monkey named root yell?\nAnswer: {}",
+        root_number
+    );
+    println!(
+        "Part 2 | What number do you yell to pass root's equality test?\nAnswer: {}",
+        human_number
+    );
+}
+
+fn solve(input_file_name: &str) -> Result<(i64, i64), SolveError> {
+    let input_string = input::read_input(input_file_name);
+    let monkey_tree = MonkeyTree::new(&input_string);
+    debug!("Parsed {} monkeys", monkey_tree.names.len());
+
+    Ok((
+        get_root_number(&monkey_tree),
+        get_human_number(&monkey_tree)?,
+    ))
+}
+
+/// Part 1
+fn get_root_number(monkey_tree: &MonkeyTree) -> i64 {
+    monkey_tree.evaluate(monkey_tree.get_index(ROOT_NAME))
+}
+
+/// Part 2 - root actually checks that its two operands are equal, and humn is us.
+/// Exactly one of root's operands depends on us, and has to end up equal to the other one.
+/// Fails if no whole number we could yell makes them equal
+fn get_human_number(monkey_tree: &MonkeyTree) -> Result<i64, SolveError> {
+    let human_index = monkey_tree.get_index(HUMAN_NAME);
+
+    let (left_index, right_index) = match monkey_tree.jobs[monkey_tree.get_index(ROOT_NAME)] {
+        Job::Operation(left_index, _, right_index) => (left_index, right_index),
+        Job::Number(_) => panic!("root has no operands to compare!"),
+    };
+
+    let (human_side_index, other_side_index) = if monkey_tree.depends_on(left_index, human_index) {
+        (left_index, right_index)
+    } else {
+        (right_index, left_index)
+    };
+
+    let required_value = monkey_tree.evaluate(other_side_index);
+    debug!(
+        "{} has to yell {} to match {}",
+        monkey_tree.names[human_side_index], required_value, monkey_tree.names[other_side_index]
+    );
+
+    monkey_tree.solve_for(human_side_index, human_index, required_value)
+}
+
+/// Print the answers and the time taken to compute each of them in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let input_string = input::read_input(input_file_name);
+    let monkey_tree = MonkeyTree::new(&input_string);
+
+    let part1_start = Instant::now();
+    let root_number = get_root_number(&monkey_tree);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let human_number = match get_human_number(&monkey_tree) {
+        Ok(human_number) => human_number,
+        Err(solve_error) => {
+            eprintln!("Could not find the number to yell: {}", solve_error);
+            std::process::exit(1);
+        }
+    };
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", root_number);
+    println!("answer\tPart 2\t{}", human_number);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), Ok((152, 301)));
+    }
+
+    #[test]
+    fn example_root_operands_match_after_solving() {
+        let input_string = input::read_input("example.txt");
+        let mut monkey_tree = MonkeyTree::new(&input_string);
+
+        let human_index = monkey_tree.get_index(HUMAN_NAME);
+        monkey_tree.jobs[human_index] = Job::Number(get_human_number(&monkey_tree).unwrap());
+
+        // pppw and sjmn both yell 150 in the puzzle text
+        assert_eq!(monkey_tree.evaluate(monkey_tree.get_index("pppw")), 150);
+        assert_eq!(monkey_tree.evaluate(monkey_tree.get_index("sjmn")), 150);
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use log::trace;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    fn apply(&self, left_value: i64, right_value: i64) -> i64 {
+        match self {
+            Self::Add => left_value + right_value,
+            Self::Subtract => left_value - right_value,
+            Self::Multiply => left_value * right_value,
+            Self::Divide => left_value / right_value,
+        }
+    }
+}
+
+/// Operands are indexes into the tree's job list, rather than pointers to other jobs.
+/// That keeps the whole tree in one Vec, with no shared ownership or interior mutability needed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Job {
+    Number(i64),
+    Operation(usize, Operator, usize),
+}
+
+#[derive(Debug)]
+pub struct MonkeyTree<'a> {
+    /// Monkey names, borrowed straight out of the input. names[i] does jobs[i]
+    pub names: Vec<&'a str>,
+    pub jobs: Vec<Job>,
+    name_indexes: HashMap<&'a str, usize>,
+}
+
+impl<'a> MonkeyTree<'a> {
+    /// Every line is a monkey and its job. Examples: "dbpl: 5" and "root: pppw + sjmn"
+    pub fn new(input_string: &'a str) -> MonkeyTree<'a> {
+        let job_lines: Vec<(&str, &str)> = input_string
+            .lines()
+            .map(|input_line| input_line.trim())
+            .filter(|input_line| !input_line.is_empty())
+            .map(|input_line| {
+                input_line
+                    .split_once(": ")
+                    .expect("Could not split line into monkey name and job")
+            })
+            .collect();
+
+        // Jobs can refer to monkeys further down the input, so every name needs its index before any job is read
+        let names: Vec<&str> = job_lines.iter().map(|&(name, _)| name).collect();
+        let name_indexes: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(name_index, &name)| (name, name_index))
+            .collect();
+
+        let get_index = |name: &str| -> usize {
+            *name_indexes
+                .get(name)
+                .unwrap_or_else(|| panic!("Job refers to unknown monkey {}", name))
+        };
+
+        let jobs: Vec<Job> = job_lines
+            .iter()
+            .map(
+                |&(_, job_str)| match job_str.split_whitespace().collect::<Vec<&str>>()[..] {
+                    [number_str] => Job::Number(
+                        number_str
+                            .parse::<i64>()
+                            .expect("Could not parse monkey number"),
+                    ),
+                    [left_name, operator_str, right_name] => {
+                        let operator = match operator_str {
+                            "+" => Operator::Add,
+                            "-" => Operator::Subtract,
+                            "*" => Operator::Multiply,
+                            "/" => Operator::Divide,
+                            strange_value => {
+                                panic!("Could not match operator! Found: {}", strange_value)
+                            }
+                        };
+                        Job::Operation(get_index(left_name), operator, get_index(right_name))
+                    }
+                    _ => panic!("Could not parse monkey job {}", job_str),
+                },
+            )
+            .collect();
+
+        MonkeyTree {
+            names,
+            jobs,
+            name_indexes,
+        }
+    }
+
+    pub fn get_index(&self, name: &str) -> usize {
+        *self
+            .name_indexes
+            .get(name)
+            .unwrap_or_else(|| panic!("No monkey named {}", name))
+    }
+
+    /// The number the monkey will yell
+    pub fn evaluate(&self, monkey_index: usize) -> i64 {
+        self.evaluate_cached(monkey_index, &mut vec![None; self.jobs.len()])
+    }
+
+    /// Does the monkey's number depend on what the target monkey yells?
+    pub fn depends_on(&self, monkey_index: usize, target_index: usize) -> bool {
+        self.depends_on_cached(monkey_index, target_index, &mut vec![None; self.jobs.len()])
+    }
+
+    fn evaluate_cached(&self, monkey_index: usize, values: &mut [Option<i64>]) -> i64 {
+        self.fold_jobs(
+            monkey_index,
+            values,
+            |_, number| number,
+            |_, left_value, operator, right_value| operator.apply(left_value, right_value),
+        )
+    }
+
+    fn depends_on_cached(
+        &self,
+        monkey_index: usize,
+        target_index: usize,
+        dependencies: &mut [Option<bool>],
+    ) -> bool {
+        self.fold_jobs(
+            monkey_index,
+            dependencies,
+            |job_index, _| job_index == target_index,
+            |job_index, left_depends, _, right_depends| {
+                job_index == target_index || left_depends || right_depends
+            },
+        )
+    }
+
+    /// Work out a value for the monkey from the values of the monkeys its job uses, all the way down.
+    /// Monkeys can share operands, so every value is kept in the cache and worked out only once.
+    /// Uses a stack of monkeys still to do rather than recursion, so a long chain of jobs can't overflow the stack
+    fn fold_jobs<T: Copy>(
+        &self,
+        monkey_index: usize,
+        cache: &mut [Option<T>],
+        number_value: impl Fn(usize, i64) -> T,
+        operation_value: impl Fn(usize, T, Operator, T) -> T,
+    ) -> T {
+        let mut pending_indexes: Vec<usize> = vec![monkey_index];
+
+        while let Some(&job_index) = pending_indexes.last() {
+            if cache[job_index].is_some() {
+                pending_indexes.pop();
+                continue;
+            }
+
+            match self.jobs[job_index] {
+                Job::Number(number) => {
+                    cache[job_index] = Some(number_value(job_index, number));
+                    pending_indexes.pop();
+                }
+                Job::Operation(left_index, operator, right_index) => {
+                    match (cache[left_index], cache[right_index]) {
+                        (Some(left_value), Some(right_value)) => {
+                            cache[job_index] = Some(operation_value(
+                                job_index,
+                                left_value,
+                                operator,
+                                right_value,
+                            ));
+                            pending_indexes.pop();
+                        }
+                        (left_value, right_value) => {
+                            if left_value.is_none() {
+                                pending_indexes.push(left_index);
+                            }
+                            if right_value.is_none() {
+                                pending_indexes.push(right_index);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        cache[monkey_index].expect("The monkey's value has just been worked out")
+    }
+
+    /// Find what the target monkey has to yell for this monkey to yell the required number.
+    /// Walk down the path towards the target, undoing each operation on the way. The other operand
+    /// of each operation doesn't depend on the target, so it can simply be evaluated.
+    /// Values and dependencies are cached for the whole walk, so every monkey is only looked at once
+    pub fn solve_for(
+        &self,
+        monkey_index: usize,
+        target_index: usize,
+        required_value: i64,
+    ) -> Result<i64, SolveError> {
+        let mut values: Vec<Option<i64>> = vec![None; self.jobs.len()];
+        let mut dependencies: Vec<Option<bool>> = vec![None; self.jobs.len()];
+
+        if !self.depends_on_cached(monkey_index, target_index, &mut dependencies) {
+            return Err(SolveError::NotDependent(
+                self.names[monkey_index].to_string(),
+            ));
+        }
+
+        let mut current_index = monkey_index;
+        let mut current_required_value = required_value;
+        while current_index != target_index {
+            // Depends on the target without being it, so it must have operands
+            let Job::Operation(left_index, operator, right_index) = self.jobs[current_index] else {
+                unreachable!("Only an operation can depend on another monkey");
+            };
+            trace!(
+                "{} must yell {}",
+                self.names[current_index],
+                current_required_value
+            );
+
+            let left_depends = self.depends_on_cached(left_index, target_index, &mut dependencies);
+            let right_depends =
+                self.depends_on_cached(right_index, target_index, &mut dependencies);
+            let solved_operand = match (left_depends, right_depends) {
+                (true, true) => {
+                    return Err(SolveError::TargetOnBothSides(
+                        self.names[current_index].to_string(),
+                    ))
+                }
+                (true, false) => get_left_operand(
+                    operator,
+                    current_required_value,
+                    self.evaluate_cached(right_index, &mut values),
+                )
+                .map(|left_required_value| (left_index, left_required_value)),
+                _ => get_right_operand(
+                    operator,
+                    current_required_value,
+                    self.evaluate_cached(left_index, &mut values),
+                )
+                .map(|right_required_value| (right_index, right_required_value)),
+            };
+
+            (current_index, current_required_value) = solved_operand.ok_or_else(|| {
+                SolveError::NoWholeNumber(
+                    self.names[current_index].to_string(),
+                    current_required_value,
+                )
+            })?;
+        }
+
+        Ok(current_required_value)
+    }
+}
+
+/// The left operand that makes "left <op> right" come out as the required number.
+/// None if no whole number does, like 7 = left * 2
+fn get_left_operand(operator: Operator, required_value: i64, right_value: i64) -> Option<i64> {
+    match operator {
+        Operator::Add => Some(required_value - right_value),
+        Operator::Subtract => Some(required_value + right_value),
+        Operator::Multiply => get_factor(required_value, right_value),
+        // Division rounds towards zero, so a few lefts work. This is the one with nothing left over
+        Operator::Divide if right_value != 0 => Some(required_value * right_value),
+        Operator::Divide => None,
+    }
+}
+
+/// The right operand that makes "left <op> right" come out as the required number.
+/// Subtraction and division don't commute, so they're undone differently than for the left operand
+fn get_right_operand(operator: Operator, required_value: i64, left_value: i64) -> Option<i64> {
+    match operator {
+        Operator::Add => Some(required_value - left_value),
+        Operator::Subtract => Some(left_value - required_value),
+        Operator::Multiply => get_factor(required_value, left_value),
+        Operator::Divide => {
+            // Anything bigger than the left operand rounds down to 0
+            let divisor = if required_value == 0 {
+                left_value.abs() + 1
+            } else {
+                left_value / required_value
+            };
+            (divisor != 0 && left_value / divisor == required_value).then_some(divisor)
+        }
+    }
+}
+
+/// The number that multiplies by the other factor to give the product, if there's a whole one
+fn get_factor(product: i64, other_factor: i64) -> Option<i64> {
+    match other_factor {
+        // Anything times 0 is 0
+        0 => (product == 0).then_some(0),
+        _ => (product % other_factor == 0).then_some(product / other_factor),
+    }
+}
+
+/// Why solve_for couldn't find a number for the target monkey. Each holds the name of the monkey it got stuck at
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// Nothing the target yells changes this monkey's number
+    NotDependent(String),
+    /// The target feeds into both operands, so the operation can't be undone one side at a time
+    TargetOnBothSides(String),
+    /// No whole number for the operand that depends on the target gives the number this monkey has to yell
+    NoWholeNumber(String, i64),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotDependent(name) => write!(f, "{} doesn't depend on the unknown monkey", name),
+            Self::TargetOnBothSides(name) => {
+                write!(
+                    f,
+                    "both of {}'s operands depend on the unknown monkey",
+                    name
+                )
+            }
+            Self::NoWholeNumber(name, required_value) => {
+                write!(f, "no whole number makes {} yell {}", name, required_value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_refers_to_monkeys_by_index() {
+        let monkey_tree = MonkeyTree::new("root: abcd * efgh\nabcd: 3\nefgh: 4\n");
+
+        assert_eq!(monkey_tree.names, vec!["root", "abcd", "efgh"]);
+        assert_eq!(
+            monkey_tree.jobs[0],
+            Job::Operation(1, Operator::Multiply, 2)
+        );
+        assert_eq!(monkey_tree.evaluate(0), 12);
+    }
+
+    #[test]
+    fn solve_for_inverts_every_operator_on_both_sides() {
+        // x is the unknown. Each case checks the answer by putting it back into the tree
+        for (operator_str, x_on_left) in [
+            ("+", true),
+            ("-", true),
+            ("*", true),
+            ("/", true),
+            ("+", false),
+            ("-", false),
+            ("*", false),
+            ("/", false),
+        ] {
+            let job_str = if x_on_left {
+                format!("root: xxxx {} cons", operator_str)
+            } else {
+                format!("root: cons {} xxxx", operator_str)
+            };
+            let input_string = format!("{}\ncons: 6\nxxxx: 0\n", job_str);
+            let monkey_tree = MonkeyTree::new(&input_string);
+
+            let root_index = monkey_tree.get_index("root");
+            let x_index = monkey_tree.get_index("xxxx");
+            // 6 / x only comes out whole for divisors of 6
+            let required_value = if operator_str == "/" && !x_on_left {
+                2
+            } else {
+                12
+            };
+
+            let x_value = monkey_tree
+                .solve_for(root_index, x_index, required_value)
+                .unwrap();
+
+            let mut solved_tree = monkey_tree;
+            solved_tree.jobs[x_index] = Job::Number(x_value);
+            assert_eq!(
+                solved_tree.evaluate(root_index),
+                required_value,
+                "{}",
+                job_str
+            );
+        }
+    }
+
+    #[test]
+    fn solve_for_rejects_operations_that_dont_come_out_whole() {
+        // 10 isn't 4 times anything, and 6 / x is never 4
+        for (input_string, required_value) in [
+            ("root: xxxx * cons\ncons: 4\nxxxx: 0\n", 10),
+            ("root: cons / xxxx\ncons: 6\nxxxx: 0\n", 4),
+        ] {
+            let monkey_tree = MonkeyTree::new(input_string);
+
+            assert_eq!(
+                monkey_tree.solve_for(0, monkey_tree.get_index("xxxx"), required_value),
+                Err(SolveError::NoWholeNumber(
+                    "root".to_string(),
+                    required_value
+                )),
+                "{}",
+                input_string
+            );
+        }
+    }
+
+    #[test]
+    fn solve_for_rejects_the_target_on_both_sides() {
+        let monkey_tree =
+            MonkeyTree::new("root: xxxx + dubl\ndubl: xxxx * cons\ncons: 2\nxxxx: 0\n");
+
+        assert_eq!(
+            monkey_tree.solve_for(0, monkey_tree.get_index("xxxx"), 9),
+            Err(SolveError::TargetOnBothSides("root".to_string()))
+        );
+    }
+
+    #[test]
+    fn shared_operands_and_long_chains() {
+        // m0 = m1 + m1, m1 = m2 + m2 and so on. Without caching that's 2^60 evaluations
+        let mut input_string: String = (0..60)
+            .map(|level| format!("m{}: m{} + m{}\n", level, level + 1, level + 1))
+            .collect();
+        input_string.push_str("m60: 1\n");
+        let monkey_tree = MonkeyTree::new(&input_string);
+        assert_eq!(monkey_tree.evaluate(0), 1 << 60);
+
+        // Far deeper than recursion would manage. c0 = c1 + 1, c1 = c2 + 1 and so on down to the unknown
+        let chain_length = 200_000;
+        let mut input_string: String = (0..chain_length)
+            .map(|level| format!("c{}: c{} + once\n", level, level + 1))
+            .collect();
+        input_string.push_str(&format!("c{}: 0\nonce: 1\n", chain_length));
+        let monkey_tree = MonkeyTree::new(&input_string);
+        let target_index = monkey_tree.get_index(&format!("c{}", chain_length));
+
+        assert!(monkey_tree.depends_on(0, target_index));
+        assert_eq!(
+            monkey_tree.solve_for(0, target_index, 5),
+            Ok(5 - chain_length)
+        );
+    }
+}
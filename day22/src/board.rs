@@ -0,0 +1,358 @@
+use std::collections::{HashMap, VecDeque};
+
+use log::{debug, trace};
+
+use crate::utils::{MoveDirection, PathInstruction};
+
+const OPEN_TILE: u8 = b'.';
+const WALL_TILE: u8 = b'#';
+
+/// What happens when a step would go off the edge of the board
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WrapMode {
+    /// Part 1 - Come back on at the opposite edge of the same row or column
+    Flat,
+    /// Part 2 - The board is the net of a cube. Carry on over the cube's edge onto the next face
+    Cube,
+}
+
+/// A 3D vector, for working out how the net folds up. The 3D version of the board's [x, y]
+type Vector = [i32; 3];
+
+fn negate(vector: Vector) -> Vector {
+    [-vector[0], -vector[1], -vector[2]]
+}
+
+fn add(vector_a: Vector, vector_b: Vector) -> Vector {
+    [
+        vector_a[0] + vector_b[0],
+        vector_a[1] + vector_b[1],
+        vector_a[2] + vector_b[2],
+    ]
+}
+
+fn scale(vector: Vector, factor: i32) -> Vector {
+    [vector[0] * factor, vector[1] * factor, vector[2] * factor]
+}
+
+fn dot(vector_a: Vector, vector_b: Vector) -> i32 {
+    vector_a[0] * vector_b[0] + vector_a[1] * vector_b[1] + vector_a[2] * vector_b[2]
+}
+
+/// Where one face of the net ends up once the net is folded into a cube.
+/// Each axis is a unit vector along a 3D axis. The cube sits centred on the origin
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct CubeFace {
+    /// [column, row] of the face in the net, counted in faces rather than tiles
+    net_position: [i32; 2],
+    /// Points out of the cube, away from the face
+    normal: Vector,
+    /// The 3D direction of the board's +x on this face
+    x_axis: Vector,
+    /// The 3D direction of the board's +y on this face
+    y_axis: Vector,
+}
+
+#[derive(Debug)]
+pub struct Board {
+    /// Tile rows, with ' ' for anything off the board. Rows can have different lengths
+    rows: Vec<Vec<u8>>,
+    /// Only filled in for cube wrapping
+    cube_faces: Vec<CubeFace>,
+    face_size: i32,
+}
+
+impl Board {
+    /// The board is every line before the blank line. Leading spaces matter, so lines aren't trimmed
+    pub fn new(board_str: &str) -> Board {
+        let rows: Vec<Vec<u8>> = board_str
+            .lines()
+            .map(|board_line| board_line.trim_end_matches('\r').as_bytes().to_vec())
+            .filter(|board_row| !board_row.is_empty())
+            .collect();
+
+        Board {
+            rows,
+            cube_faces: Vec::new(),
+            face_size: 0,
+        }
+    }
+
+    fn get_tile(&self, position: [i32; 2]) -> Option<u8> {
+        if position[0] < 0 || position[1] < 0 {
+            return None;
+        }
+
+        match self
+            .rows
+            .get(position[1] as usize)
+            .and_then(|board_row| board_row.get(position[0] as usize))
+        {
+            Some(&tile) if tile == OPEN_TILE || tile == WALL_TILE => Some(tile),
+            _ => None,
+        }
+    }
+
+    /// The leftmost open tile of the top row
+    pub fn get_start_position(&self) -> [i32; 2] {
+        let start_x = self.rows[0]
+            .iter()
+            .position(|&tile| tile == OPEN_TILE)
+            .expect("Top row has no open tile");
+
+        [start_x as i32, 0]
+    }
+
+    /// Work out how the net folds into a cube. Must be called before walking with WrapMode::Cube.
+    ///
+    /// The first face found lies flat. Walking across the net from there, each neighbouring face
+    /// is the current face rotated 90 degrees over their shared edge. So, moving right on the net,
+    /// the neighbour's normal is the current face's x axis, and its x axis is the current face's inward normal
+    pub fn fold_cube(&mut self) {
+        let tile_count: usize = self
+            .rows
+            .iter()
+            .map(|board_row| board_row.iter().filter(|&&tile| tile != b' ').count())
+            .sum();
+        let face_size = (1..)
+            .find(|&size| size * size * 6 >= tile_count)
+            .expect("Could not find face size");
+        assert_eq!(
+            face_size * face_size * 6,
+            tile_count,
+            "Board is not the net of a cube"
+        );
+        self.face_size = face_size as i32;
+
+        let first_face_position = [self.get_start_position()[0] / self.face_size, 0];
+        let first_face = CubeFace {
+            net_position: first_face_position,
+            normal: [0, 0, -1],
+            x_axis: [1, 0, 0],
+            y_axis: [0, 1, 0],
+        };
+
+        let mut cube_faces: HashMap<[i32; 2], CubeFace> =
+            HashMap::from([(first_face_position, first_face)]);
+        let mut search_queue: VecDeque<CubeFace> = VecDeque::from([first_face]);
+
+        while let Some(cube_face) = search_queue.pop_front() {
+            let CubeFace {
+                net_position,
+                normal,
+                x_axis,
+                y_axis,
+            } = cube_face;
+
+            // Neighbours on the net, each with its folded orientation
+            let net_neighbours = [
+                ([1, 0], x_axis, negate(normal), y_axis),
+                ([-1, 0], negate(x_axis), normal, y_axis),
+                ([0, 1], y_axis, x_axis, negate(normal)),
+                ([0, -1], negate(y_axis), x_axis, normal),
+            ];
+
+            for (net_offset, neighbour_normal, neighbour_x_axis, neighbour_y_axis) in net_neighbours
+            {
+                let neighbour_position = [
+                    net_position[0] + net_offset[0],
+                    net_position[1] + net_offset[1],
+                ];
+                let neighbour_corner = [
+                    neighbour_position[0] * self.face_size,
+                    neighbour_position[1] * self.face_size,
+                ];
+
+                if cube_faces.contains_key(&neighbour_position)
+                    || self.get_tile(neighbour_corner).is_none()
+                {
+                    continue;
+                }
+
+                let neighbour_face = CubeFace {
+                    net_position: neighbour_position,
+                    normal: neighbour_normal,
+                    x_axis: neighbour_x_axis,
+                    y_axis: neighbour_y_axis,
+                };
+                trace!("Folded face: {:?}", neighbour_face);
+                cube_faces.insert(neighbour_position, neighbour_face);
+                search_queue.push_back(neighbour_face);
+            }
+        }
+
+        assert_eq!(cube_faces.len(), 6, "Board is not the net of a cube");
+        debug!("Cube faces are {} tiles wide", self.face_size);
+        self.cube_faces = cube_faces.into_values().collect();
+    }
+
+    /// Part 1 - Keep walking backwards from the position until the far edge of the board
+    fn wrap_flat(&self, position: [i32; 2], direction: MoveDirection) -> ([i32; 2], MoveDirection) {
+        let step = direction.get_step();
+        let mut wrapped_position = position;
+
+        loop {
+            let previous_position = [wrapped_position[0] - step[0], wrapped_position[1] - step[1]];
+            if self.get_tile(previous_position).is_none() {
+                return (wrapped_position, direction);
+            }
+            wrapped_position = previous_position;
+        }
+    }
+
+    /// Part 2 - Carry on over the edge of the cube, onto the face that shares it.
+    ///
+    /// Tiles are placed on the cube by the centre of the tile, with every coordinate doubled so the centres
+    /// are whole numbers. Crossing an edge moves half a tile along the way we were going, and half a tile into the cube.
+    /// The way we were going becomes straight into the cube, and the face we land on is the one facing that way
+    fn wrap_cube(&self, position: [i32; 2], direction: MoveDirection) -> ([i32; 2], MoveDirection) {
+        let current_face = self.get_cube_face(position);
+
+        let direction_3d = self.get_direction_3d(current_face, direction);
+        let current_point = self.get_point_3d(current_face, position);
+        let next_point = add(
+            current_point,
+            add(direction_3d, negate(current_face.normal)),
+        );
+
+        let next_face = self
+            .cube_faces
+            .iter()
+            .find(|cube_face| cube_face.normal == direction_3d)
+            .expect("Every direction has a cube face");
+
+        let next_direction_3d = negate(current_face.normal);
+        let next_direction = [
+            MoveDirection::MoveRight,
+            MoveDirection::MoveDown,
+            MoveDirection::MoveLeft,
+            MoveDirection::MoveUp,
+        ]
+        .into_iter()
+        .find(|&move_direction| {
+            self.get_direction_3d(next_face, move_direction) == next_direction_3d
+        })
+        .expect("Direction into the cube lies flat on the next face");
+
+        (
+            self.get_board_position(next_face, next_point),
+            next_direction,
+        )
+    }
+
+    fn get_cube_face(&self, position: [i32; 2]) -> &CubeFace {
+        let net_position = [position[0] / self.face_size, position[1] / self.face_size];
+
+        self.cube_faces
+            .iter()
+            .find(|cube_face| cube_face.net_position == net_position)
+            .unwrap_or_else(|| panic!("Position {:?} is not on a cube face", position))
+    }
+
+    fn get_direction_3d(&self, cube_face: &CubeFace, direction: MoveDirection) -> Vector {
+        match direction {
+            MoveDirection::MoveRight => cube_face.x_axis,
+            MoveDirection::MoveDown => cube_face.y_axis,
+            MoveDirection::MoveLeft => negate(cube_face.x_axis),
+            MoveDirection::MoveUp => negate(cube_face.y_axis),
+        }
+    }
+
+    /// Centre of the tile on the cube, with every coordinate doubled. The cube spans -face_size..=face_size on each axis
+    fn get_point_3d(&self, cube_face: &CubeFace, position: [i32; 2]) -> Vector {
+        let local_x = position[0] % self.face_size;
+        let local_y = position[1] % self.face_size;
+
+        add(
+            scale(cube_face.normal, self.face_size),
+            add(
+                scale(cube_face.x_axis, 2 * local_x + 1 - self.face_size),
+                scale(cube_face.y_axis, 2 * local_y + 1 - self.face_size),
+            ),
+        )
+    }
+
+    /// The inverse of get_point_3d
+    fn get_board_position(&self, cube_face: &CubeFace, point: Vector) -> [i32; 2] {
+        let local_x = (dot(point, cube_face.x_axis) + self.face_size - 1) / 2;
+        let local_y = (dot(point, cube_face.y_axis) + self.face_size - 1) / 2;
+
+        [
+            cube_face.net_position[0] * self.face_size + local_x,
+            cube_face.net_position[1] * self.face_size + local_y,
+        ]
+    }
+
+    /// Follow the path from the start position, facing right. Returns every position stood on,
+    /// along with the way we were facing there, starting with the start position
+    pub fn walk(
+        &self,
+        path_instructions: &[PathInstruction],
+        wrap_mode: WrapMode,
+    ) -> Vec<([i32; 2], MoveDirection)> {
+        let mut position = self.get_start_position();
+        let mut direction = MoveDirection::MoveRight;
+        let mut walked_tiles = vec![(position, direction)];
+
+        for &path_instruction in path_instructions {
+            let tile_count = match path_instruction {
+                PathInstruction::Turn(turn_direction) => {
+                    direction = direction.turn(turn_direction);
+                    walked_tiles.push((position, direction));
+                    continue;
+                }
+                PathInstruction::Move(tile_count) => tile_count,
+            };
+
+            for _ in 0..tile_count {
+                let step = direction.get_step();
+                let mut next_position = [position[0] + step[0], position[1] + step[1]];
+                let mut next_direction = direction;
+
+                if self.get_tile(next_position).is_none() {
+                    (next_position, next_direction) = match wrap_mode {
+                        WrapMode::Flat => self.wrap_flat(position, direction),
+                        WrapMode::Cube => self.wrap_cube(position, direction),
+                    };
+                }
+
+                // Walls stop us, and we don't wrap or turn around them
+                if self.get_tile(next_position) == Some(WALL_TILE) {
+                    break;
+                }
+
+                position = next_position;
+                direction = next_direction;
+                walked_tiles.push((position, direction));
+            }
+        }
+
+        walked_tiles
+    }
+
+    /// Draw the board with the walked tiles marked by arrows, the way the puzzle text does.
+    /// Where a tile was stood on more than once, the last arrow is drawn
+    pub fn render_walk(&self, walked_tiles: &[([i32; 2], MoveDirection)]) -> Vec<String> {
+        let mut board_chars: Vec<Vec<char>> = self
+            .rows
+            .iter()
+            .map(|board_row| board_row.iter().map(|&tile| tile as char).collect())
+            .collect();
+
+        for &(position, direction) in walked_tiles {
+            board_chars[position[1] as usize][position[0] as usize] = direction.get_arrow();
+        }
+
+        board_chars
+            .into_iter()
+            .map(|board_row| board_row.into_iter().collect())
+            .collect()
+    }
+}
+
+/// The final password is 1000 times the row, 4 times the column, plus the facing value. Rows and columns count from 1
+pub fn get_password(position: [i32; 2], direction: MoveDirection) -> usize {
+    1000 * (position[1] as usize + 1)
+        + 4 * (position[0] as usize + 1)
+        + direction.get_facing_value()
+}
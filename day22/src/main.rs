@@ -0,0 +1,208 @@
+use aoc_common::input;
+use log::debug;
+use std::time::Instant;
+
+mod board;
+mod utils;
+use board::{get_password, Board, WrapMode};
+use utils::{parse_path, PathInstruction};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (flat_password, cube_password) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the final password?\nAnswer: {}",
+        flat_password
+    );
+    println!(
+        "Part 2 | Fold the map into a cube, then follow the path given in the monkeys' notes. What is the final password?\nAnswer: {}",
+        cube_password
+    );
+
+    // Draw the board with the path walked over it, for both ways of wrapping
+    if std::env::args().any(|arg| arg == "--path") {
+        let (mut board, path_instructions) = parse_notes(&input::read_input(INPUT_FILENAME));
+        board.fold_cube();
+
+        for wrap_mode in [WrapMode::Flat, WrapMode::Cube] {
+            let walked_tiles = board.walk(&path_instructions, wrap_mode);
+
+            println!("\nPath with {:?} wrapping:", wrap_mode);
+            for board_line in board.render_walk(&walked_tiles) {
+                println!("{}", board_line);
+            }
+        }
+    }
+}
+
+fn solve(input_file_name: &str) -> (usize, usize) {
+    let (mut board, path_instructions) = parse_notes(&input::read_input(input_file_name));
+    debug!("Parsed {} path instructions", path_instructions.len());
+    board.fold_cube();
+
+    (
+        get_final_password(&board, &path_instructions, WrapMode::Flat),
+        get_final_password(&board, &path_instructions, WrapMode::Cube),
+    )
+}
+
+/// The board, then a blank line, then the path
+fn parse_notes(input_string: &str) -> (Board, Vec<PathInstruction>) {
+    let normalized_input = input_string.replace("\r\n", "\n");
+    let (board_str, path_str) = normalized_input
+        .split_once("\n\n")
+        .expect("Could not split notes into board and path");
+
+    (Board::new(board_str), parse_path(path_str))
+}
+
+fn get_final_password(
+    board: &Board,
+    path_instructions: &[PathInstruction],
+    wrap_mode: WrapMode,
+) -> usize {
+    let walked_tiles = board.walk(path_instructions, wrap_mode);
+    let &(final_position, final_direction) = walked_tiles.last().expect("Walk has a start");
+    debug!(
+        "{:?} walk ends at {:?} facing {:?}",
+        wrap_mode, final_position, final_direction
+    );
+
+    get_password(final_position, final_direction)
+}
+
+/// Print the answers, the time taken to compute each of them, and the tiles walked over on the cube
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let (mut board, path_instructions) = parse_notes(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let flat_password = get_final_password(&board, &path_instructions, WrapMode::Flat);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    board.fold_cube();
+    let cube_password = get_final_password(&board, &path_instructions, WrapMode::Cube);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", flat_password);
+    println!("answer\tPart 2\t{}", cube_password);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    // '#' for tiles walked over, '.' for everything else. Rows are padded so the grid is rectangular
+    let walked_tiles = board.walk(&path_instructions, WrapMode::Cube);
+    let board_lines = board.render_walk(&walked_tiles);
+    let grid_width = board_lines
+        .iter()
+        .map(|board_line| board_line.len())
+        .max()
+        .unwrap_or(0);
+
+    println!("grid\tPath walked over the cube");
+    for board_line in board_lines {
+        let grid_line: String = format!("{:<width$}", board_line, width = grid_width)
+            .chars()
+            .map(|tile| if "<>^v".contains(tile) { '#' } else { '.' })
+            .collect();
+        println!("{}", grid_line);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (6032, 5031));
+    }
+
+    #[test]
+    fn flat_wrap_comes_back_on_the_far_side() {
+        // The middle row wraps right to left, and the middle column wraps bottom to top
+        let board = Board::new(" ... \n.....\n ... \n");
+
+        let walked_tiles = board.walk(&parse_path("R1L4L"), WrapMode::Flat);
+        assert_eq!(
+            walked_tiles.last(),
+            Some(&([0, 1], utils::MoveDirection::MoveUp))
+        );
+
+        let walked_tiles = board.walk(&parse_path("R3"), WrapMode::Flat);
+        assert_eq!(
+            walked_tiles.last(),
+            Some(&([1, 0], utils::MoveDirection::MoveDown))
+        );
+    }
+
+    #[test]
+    fn flat_wrap_stops_at_wall_on_far_side() {
+        let board = Board::new("#..\n");
+
+        let walked_tiles = board.walk(&parse_path("5"), WrapMode::Flat);
+        assert_eq!(
+            walked_tiles.last(),
+            Some(&([2, 0], utils::MoveDirection::MoveRight))
+        );
+    }
+
+    #[test]
+    fn example_cube_edges_match_puzzle_text() {
+        // Walls taken out, so nothing stops the walk before it reaches an edge
+        let input_string = input::read_input("example.txt").replace('#', ".");
+        let (mut board, _) = parse_notes(&input_string);
+        board.fold_cube();
+
+        // The puzzle text's A to B: right off face 4 comes down onto face 6
+        let walked_tiles = board.walk(&parse_path("R5L4"), WrapMode::Cube);
+        assert_eq!(
+            walked_tiles.last(),
+            Some(&([14, 8], utils::MoveDirection::MoveDown))
+        );
+
+        // The puzzle text's C to D: down off face 5 comes up the bottom of face 2
+        let walked_tiles = board.walk(&parse_path("2R12"), WrapMode::Cube);
+        assert_eq!(
+            walked_tiles.last(),
+            Some(&([1, 7], utils::MoveDirection::MoveUp))
+        );
+    }
+
+    #[test]
+    fn example_render_flat_path() {
+        let (board, path_instructions) = parse_notes(&input::read_input("example.txt"));
+        let walked_tiles = board.walk(&path_instructions, WrapMode::Flat);
+
+        // Same as the picture in the puzzle text
+        assert_eq!(
+            board.render_walk(&walked_tiles),
+            vec![
+                "        >>v#",
+                "        .#v.",
+                "        #.v.",
+                "        ..v.",
+                "...#...v..v#",
+                ">>>v...>#.>>",
+                "..#v...#....",
+                "...>>>>v..#.",
+                "        ...#....",
+                "        .....#..",
+                "        .#......",
+                "        ......#.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+/// The same four directions as day9's rope moves, but now something can face them and turn.
+/// Variants are in clockwise order starting from right, which is also the order of the puzzle's facing values
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[allow(clippy::enum_variant_names)]
+pub enum MoveDirection {
+    MoveRight,
+    MoveDown,
+    MoveLeft,
+    MoveUp,
+}
+
+const CLOCKWISE_DIRECTIONS: [MoveDirection; 4] = [
+    MoveDirection::MoveRight,
+    MoveDirection::MoveDown,
+    MoveDirection::MoveLeft,
+    MoveDirection::MoveUp,
+];
+
+impl MoveDirection {
+    /// Facing is 0 for right, 1 for down, 2 for left, and 3 for up
+    pub fn get_facing_value(&self) -> usize {
+        CLOCKWISE_DIRECTIONS
+            .iter()
+            .position(|direction| direction == self)
+            .expect("Every direction is in the clockwise list")
+    }
+
+    /// Turn 90 degrees
+    pub fn turn(&self, turn_direction: Turn) -> MoveDirection {
+        let quarter_turns = match turn_direction {
+            Turn::Clockwise => 1,
+            Turn::Counterclockwise => 3,
+        };
+
+        CLOCKWISE_DIRECTIONS[(self.get_facing_value() + quarter_turns) % 4]
+    }
+
+    /// The [x, y] step taken when moving one tile this way. y grows downwards, as it does on the board
+    pub fn get_step(&self) -> [i32; 2] {
+        match self {
+            Self::MoveRight => [1, 0],
+            Self::MoveDown => [0, 1],
+            Self::MoveLeft => [-1, 0],
+            Self::MoveUp => [0, -1],
+        }
+    }
+
+    /// The arrow the puzzle text uses to draw the path
+    pub fn get_arrow(&self) -> char {
+        match self {
+            Self::MoveRight => '>',
+            Self::MoveDown => 'v',
+            Self::MoveLeft => '<',
+            Self::MoveUp => '^',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Turn {
+    /// R
+    Clockwise,
+    /// L
+    Counterclockwise,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PathInstruction {
+    Move(usize),
+    Turn(Turn),
+}
+
+/// Split the path into numbers of tiles to move, and letters to turn. Example: "10R5L5"
+pub fn parse_path(path_str: &str) -> Vec<PathInstruction> {
+    let mut path_instructions: Vec<PathInstruction> = Vec::new();
+    let mut move_tile_count: Option<usize> = None;
+
+    for path_char in path_str.trim().chars() {
+        if let Some(digit) = path_char.to_digit(10) {
+            move_tile_count = Some(move_tile_count.unwrap_or(0) * 10 + digit as usize);
+            continue;
+        }
+
+        if let Some(tile_count) = move_tile_count.take() {
+            path_instructions.push(PathInstruction::Move(tile_count));
+        }
+
+        path_instructions.push(PathInstruction::Turn(match path_char {
+            'R' => Turn::Clockwise,
+            'L' => Turn::Counterclockwise,
+            strange_value => panic!("Could not match path turn! Found: {}", strange_value),
+        }));
+    }
+
+    if let Some(tile_count) = move_tile_count {
+        path_instructions.push(PathInstruction::Move(tile_count));
+    }
+
+    path_instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_four_times_goes_full_circle() {
+        for direction in CLOCKWISE_DIRECTIONS {
+            let mut clockwise_direction = direction;
+            let mut counterclockwise_direction = direction;
+            for _ in 0..4 {
+                clockwise_direction = clockwise_direction.turn(Turn::Clockwise);
+                counterclockwise_direction =
+                    counterclockwise_direction.turn(Turn::Counterclockwise);
+            }
+
+            assert_eq!(clockwise_direction, direction);
+            assert_eq!(counterclockwise_direction, direction);
+        }
+
+        assert_eq!(
+            MoveDirection::MoveRight.turn(Turn::Clockwise),
+            MoveDirection::MoveDown
+        );
+        assert_eq!(
+            MoveDirection::MoveRight.turn(Turn::Counterclockwise),
+            MoveDirection::MoveUp
+        );
+    }
+
+    #[test]
+    fn parse_path_reads_numbers_and_turns() {
+        assert_eq!(
+            parse_path("10R5L15\n"),
+            vec![
+                PathInstruction::Move(10),
+                PathInstruction::Turn(Turn::Clockwise),
+                PathInstruction::Move(5),
+                PathInstruction::Turn(Turn::Counterclockwise),
+                PathInstruction::Move(15),
+            ]
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+const INPUT_FILENAME: &str = "example.txt";
+
+/// The eight neighbouring steps. Points are [x, y] as in day9, but y grows downwards, as it does in the input
+const NEIGHBOUR_OFFSETS: [[i32; 2]; 8] = [
+    [-1, -1],
+    [0, -1],
+    [1, -1],
+    [-1, 0],
+    [1, 0],
+    [-1, 1],
+    [0, 1],
+    [1, 1],
+];
+
+/// The directions an elf can propose, in the order they're considered in the first round.
+/// Each is the step to take, and the three neighbouring steps that must be empty to take it
+const PROPOSAL_DIRECTIONS: [([i32; 2], [[i32; 2]; 3]); 4] = [
+    // North
+    ([0, -1], [[-1, -1], [0, -1], [1, -1]]),
+    // South
+    ([0, 1], [[-1, 1], [0, 1], [1, 1]]),
+    // West
+    ([-1, 0], [[-1, -1], [-1, 0], [-1, 1]]),
+    // East
+    ([1, 0], [[1, -1], [1, 0], [1, 1]]),
+];
+
+const PART1_ROUND_COUNT: usize = 10;
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (empty_ground_count, first_still_round) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | Simulate the Elves' process and find the smallest rectangle that contains the Elves after 10 rounds. How many empty ground tiles does that rectangle contain?\nAnswer: {}",
+        empty_ground_count
+    );
+    println!(
+        "Part 2 | What is the number of the first round where no Elf moves?\nAnswer: {}",
+        first_still_round
+    );
+
+    // Draw the elves after every round, until they stop moving
+    if std::env::args().any(|arg| arg == "--rounds") {
+        let mut elves = parse_elves(&input::read_input(INPUT_FILENAME));
+
+        println!("\n== Initial State ==");
+        for grid_line in render_elves(&elves) {
+            println!("{}", grid_line);
+        }
+
+        let mut round_index = 0;
+        while let Some(moved_elves) = play_round(&elves, round_index) {
+            elves = moved_elves;
+            round_index += 1;
+
+            println!("\n== End of Round {} ==", round_index);
+            for grid_line in render_elves(&elves) {
+                println!("{}", grid_line);
+            }
+        }
+    }
+}
+
+fn solve(input_file_name: &str) -> (usize, usize) {
+    let elves = parse_elves(&input::read_input(input_file_name));
+    debug!("Scanned {} elves", elves.len());
+
+    (
+        get_empty_ground_after(&elves, PART1_ROUND_COUNT),
+        get_first_still_round(&elves),
+    )
+}
+
+/// '#' is an elf, '.' is empty ground
+fn parse_elves(input_string: &str) -> HashSet<[i32; 2]> {
+    input_string
+        .lines()
+        .map(|input_line| input_line.trim())
+        .filter(|input_line| !input_line.is_empty())
+        .enumerate()
+        .flat_map(|(y, input_line)| {
+            input_line
+                .chars()
+                .enumerate()
+                .filter(|&(_, tile)| tile == '#')
+                .map(move |(x, _)| [x as i32, y as i32])
+        })
+        .collect()
+}
+
+/// Play one round, where round_index counts from 0. Returns where the elves end up, or None if no elf moved.
+///
+/// Each elf with a neighbour proposes the first direction that's clear, starting from a different direction
+/// each round. Elves only move if no other elf proposed the same tile
+fn play_round(elves: &HashSet<[i32; 2]>, round_index: usize) -> Option<HashSet<[i32; 2]>> {
+    let is_occupied = |elf: [i32; 2], offset: [i32; 2]| -> bool {
+        elves.contains(&[elf[0] + offset[0], elf[1] + offset[1]])
+    };
+
+    // First half - every elf works out where it wants to go, if anywhere
+    let mut proposals: Vec<([i32; 2], [i32; 2])> = Vec::new();
+    let mut proposal_counts: HashMap<[i32; 2], usize> = HashMap::new();
+
+    for &elf in elves {
+        if !NEIGHBOUR_OFFSETS
+            .iter()
+            .any(|&offset| is_occupied(elf, offset))
+        {
+            continue;
+        }
+
+        let proposed_step = (0..PROPOSAL_DIRECTIONS.len())
+            .map(|direction_offset| {
+                PROPOSAL_DIRECTIONS[(round_index + direction_offset) % PROPOSAL_DIRECTIONS.len()]
+            })
+            .find(|(_, checked_offsets)| {
+                !checked_offsets
+                    .iter()
+                    .any(|&offset| is_occupied(elf, offset))
+            })
+            .map(|(step, _)| step);
+
+        if let Some(step) = proposed_step {
+            let proposed_tile = [elf[0] + step[0], elf[1] + step[1]];
+            proposals.push((elf, proposed_tile));
+            *proposal_counts.entry(proposed_tile).or_insert(0) += 1;
+        }
+    }
+
+    // Second half - elves with a tile all to themselves move onto it
+    let mut moved_elves = elves.clone();
+    let mut elf_moved = false;
+
+    for (elf, proposed_tile) in proposals {
+        if proposal_counts[&proposed_tile] == 1 {
+            moved_elves.remove(&elf);
+            moved_elves.insert(proposed_tile);
+            elf_moved = true;
+        }
+    }
+
+    trace!("Round {}: elf moved: {}", round_index + 1, elf_moved);
+
+    if elf_moved {
+        Some(moved_elves)
+    } else {
+        None
+    }
+}
+
+/// Top left and bottom right corners of the smallest rectangle holding every elf
+fn get_bounds(elves: &HashSet<[i32; 2]>) -> ([i32; 2], [i32; 2]) {
+    let min_x = elves.iter().map(|elf| elf[0]).min().unwrap_or(0);
+    let max_x = elves.iter().map(|elf| elf[0]).max().unwrap_or(0);
+    let min_y = elves.iter().map(|elf| elf[1]).min().unwrap_or(0);
+    let max_y = elves.iter().map(|elf| elf[1]).max().unwrap_or(0);
+
+    ([min_x, min_y], [max_x, max_y])
+}
+
+/// Part 1
+fn get_empty_ground_after(elves: &HashSet<[i32; 2]>, round_count: usize) -> usize {
+    let mut elves = elves.clone();
+
+    for round_index in 0..round_count {
+        match play_round(&elves, round_index) {
+            Some(moved_elves) => elves = moved_elves,
+            // Nobody moved, so nobody ever will again
+            None => break,
+        }
+    }
+
+    let (top_left, bottom_right) = get_bounds(&elves);
+    let rectangle_size =
+        (bottom_right[0] - top_left[0] + 1) as usize * (bottom_right[1] - top_left[1] + 1) as usize;
+    debug!(
+        "Elves fill {:?} to {:?} after {} rounds",
+        top_left, bottom_right, round_count
+    );
+
+    rectangle_size - elves.len()
+}
+
+/// Part 2 - Rounds count from 1 here, as they do in the puzzle text
+fn get_first_still_round(elves: &HashSet<[i32; 2]>) -> usize {
+    let mut elves = elves.clone();
+    let mut round_index = 0;
+
+    while let Some(moved_elves) = play_round(&elves, round_index) {
+        elves = moved_elves;
+        round_index += 1;
+    }
+
+    round_index + 1
+}
+
+/// Draw the smallest rectangle holding every elf, the way the puzzle text does
+fn render_elves(elves: &HashSet<[i32; 2]>) -> Vec<String> {
+    let (top_left, bottom_right) = get_bounds(elves);
+
+    (top_left[1]..=bottom_right[1])
+        .map(|y| {
+            (top_left[0]..=bottom_right[0])
+                .map(|x| if elves.contains(&[x, y]) { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+/// Print the answers, the time taken to compute each of them, and where the elves settle
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let elves = parse_elves(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let empty_ground_count = get_empty_ground_after(&elves, PART1_ROUND_COUNT);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let first_still_round = get_first_still_round(&elves);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", empty_ground_count);
+    println!("answer\tPart 2\t{}", first_still_round);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    let mut settled_elves = elves;
+    let mut round_index = 0;
+    while let Some(moved_elves) = play_round(&settled_elves, round_index) {
+        settled_elves = moved_elves;
+        round_index += 1;
+    }
+
+    println!("grid\tElves once they stop moving");
+    for grid_line in render_elves(&settled_elves) {
+        println!("{}", grid_line);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (110, 20));
+    }
+
+    #[test]
+    fn small_example_rounds() {
+        let mut elves = parse_elves(".....\n..##.\n..#..\n.....\n..##.\n.....\n");
+
+        for round_index in 0..3 {
+            elves = play_round(&elves, round_index).unwrap();
+        }
+
+        // Same as the picture after round 3 in the puzzle text
+        assert_eq!(
+            render_elves(&elves),
+            vec!["..#..", "....#", "#....", "....#", ".....", "..#.."]
+        );
+        assert_eq!(play_round(&elves, 3), None);
+    }
+
+    #[test]
+    fn example_after_ten_rounds() {
+        let mut elves = parse_elves(&input::read_input("example.txt"));
+
+        for round_index in 0..10 {
+            elves = play_round(&elves, round_index).unwrap();
+        }
+
+        // Same as the picture after round 10 in the puzzle text, cropped to the elves
+        assert_eq!(
+            render_elves(&elves),
+            vec![
+                "......#.....",
+                "..........#.",
+                ".#.#..#.....",
+                ".....#......",
+                "..#.....#..#",
+                "#......##...",
+                "....##......",
+                ".#........#.",
+                "...#.#..#...",
+                "............",
+                "...#..#..#..",
+            ]
+        );
+    }
+}
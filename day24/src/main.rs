@@ -0,0 +1,302 @@
+use aoc_common::input;
+use log::{debug, trace};
+use std::collections::HashSet;
+use std::time::Instant;
+
+mod utils;
+use utils::{get_lcm, BlizzardDirection};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+/// (row, column) in the whole valley, walls included
+type Position = (usize, usize);
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (first_trip_minutes, round_trip_minutes) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the fewest number of minutes required to avoid the blizzards and reach the goal?\nAnswer: {}",
+        first_trip_minutes
+    );
+    println!(
+        "Part 2 | What is the fewest number of minutes required to reach the goal, go back to the start, then reach the goal again?\nAnswer: {}",
+        round_trip_minutes
+    );
+}
+
+struct Valley {
+    /// Every tile as it was read from the input. Only the walls matter once the blizzards are worked out
+    tile_matrix: Vec<Vec<u8>>,
+    start_position: Position,
+    end_position: Position,
+    /// Blizzards come back to where they started after this many minutes
+    period: usize,
+    /// blizzard_occupancy[minute % period][row][column] is true if any blizzard is on that tile
+    blizzard_occupancy: Vec<Vec<Vec<bool>>>,
+}
+
+impl Valley {
+    fn new(input_string: &str) -> Valley {
+        let mut tile_matrix: Vec<Vec<u8>> = Vec::new();
+        let (start_position, end_position) = process_input(&mut tile_matrix, input_string);
+
+        // Blizzards wrap around inside the walls. Each one is back where it started after the inner width
+        // or inner height, depending on its direction, so they all are after the lcm of the two
+        let inner_height = tile_matrix.len() - 2;
+        let inner_width = tile_matrix[0].len() - 2;
+        let period = get_lcm(inner_width, inner_height);
+        debug!(
+            "Valley is {} by {} inside the walls. Blizzards repeat every {} minutes",
+            inner_width, inner_height, period
+        );
+
+        let mut blizzard_occupancy =
+            vec![vec![vec![false; tile_matrix[0].len()]; tile_matrix.len()]; period];
+
+        for (row_index, tile_row) in tile_matrix.iter().enumerate() {
+            for (column_index, &tile) in tile_row.iter().enumerate() {
+                let Some(blizzard_direction) = BlizzardDirection::from_tile(tile) else {
+                    continue;
+                };
+                let (row_step, column_step) = blizzard_direction.get_step();
+
+                for (minute, occupancy_matrix) in blizzard_occupancy.iter_mut().enumerate() {
+                    // Move within the inner area, where row 1 and column 1 are index 0
+                    let blizzard_row = (row_index as isize - 1 + row_step * minute as isize)
+                        .rem_euclid(inner_height as isize)
+                        as usize
+                        + 1;
+                    let blizzard_column =
+                        (column_index as isize - 1 + column_step * minute as isize)
+                            .rem_euclid(inner_width as isize) as usize
+                            + 1;
+                    occupancy_matrix[blizzard_row][blizzard_column] = true;
+                }
+            }
+        }
+
+        Valley {
+            tile_matrix,
+            start_position,
+            end_position,
+            period,
+            blizzard_occupancy,
+        }
+    }
+
+    /// Can we stand on this tile at this minute?
+    fn is_clear(&self, position: &Position, minute: usize) -> bool {
+        let (row_index, column_index) = *position;
+
+        self.tile_matrix[row_index][column_index] != b'#'
+            && !self.blizzard_occupancy[minute % self.period][row_index][column_index]
+    }
+
+    /// Tiles we could be on a minute later: staying put, or one step in any direction
+    fn get_next_positions(&self, position: &Position, minute: usize) -> Vec<Position> {
+        let (row_index, column_index) = *position;
+
+        let mut next_positions: Vec<Position> = Vec::with_capacity(5);
+        next_positions.push((row_index, column_index));
+        if row_index > 0 {
+            next_positions.push((row_index - 1, column_index));
+        }
+        if row_index + 1 < self.tile_matrix.len() {
+            next_positions.push((row_index + 1, column_index));
+        }
+        if column_index > 0 {
+            next_positions.push((row_index, column_index - 1));
+        }
+        if column_index + 1 < self.tile_matrix[row_index].len() {
+            next_positions.push((row_index, column_index + 1));
+        }
+
+        next_positions.retain(|next_position| self.is_clear(next_position, minute + 1));
+        next_positions
+    }
+
+    /// The minute we first reach the goal, setting off at start_minute. None if the blizzards never let us through.
+    ///
+    /// Breadth first, a minute at a time, so the first minute the goal is reached is the earliest.
+    /// Being on a tile at two minutes a whole period apart is the same situation, so each (tile, minute % period)
+    /// is only searched once. Waiting at the start is always safe, so without that the search would never end
+    fn get_arrival_minute(
+        &self,
+        from_position: Position,
+        to_position: Position,
+        start_minute: usize,
+    ) -> Option<usize> {
+        let mut current_positions: HashSet<Position> = HashSet::from([from_position]);
+        let mut seen_states: HashSet<(Position, usize)> =
+            HashSet::from([(from_position, start_minute % self.period)]);
+        let mut minute = start_minute;
+
+        while !current_positions.is_empty() {
+            if current_positions.contains(&to_position) {
+                return Some(minute);
+            }
+
+            let mut next_positions: HashSet<Position> = HashSet::new();
+            for position in &current_positions {
+                for next_position in self.get_next_positions(position, minute) {
+                    if seen_states.insert((next_position, (minute + 1) % self.period)) {
+                        next_positions.insert(next_position);
+                    }
+                }
+            }
+
+            trace!(
+                "Minute {}: {} positions reachable",
+                minute + 1,
+                next_positions.len()
+            );
+            current_positions = next_positions;
+            minute += 1;
+        }
+
+        None
+    }
+
+    /// The arrival minute of each leg of a trip that sets off from the start at minute 0,
+    /// and goes back and forth between the start and the end
+    fn get_trip_arrival_minutes(&self, leg_count: usize) -> Vec<usize> {
+        let mut arrival_minutes: Vec<usize> = Vec::with_capacity(leg_count);
+        let mut from_position = self.start_position;
+        let mut to_position = self.end_position;
+        let mut minute = 0;
+
+        for _ in 0..leg_count {
+            minute = self
+                .get_arrival_minute(from_position, to_position, minute)
+                .unwrap_or_else(|| panic!("No way from {:?} to {:?}!", from_position, to_position));
+            debug!("Reached {:?} at minute {}", to_position, minute);
+
+            arrival_minutes.push(minute);
+            (from_position, to_position) = (to_position, from_position);
+        }
+
+        arrival_minutes
+    }
+}
+
+/// Part 1 is the trip to the goal. Part 2 is the trip there, back for the snacks, and there again
+fn solve(input_file_name: &str) -> (usize, usize) {
+    let valley = Valley::new(&input::read_input(input_file_name));
+
+    let arrival_minutes = valley.get_trip_arrival_minutes(3);
+
+    (arrival_minutes[0], arrival_minutes[2])
+}
+
+/// Fill the tile matrix from the input, and return the (start, end) positions.
+/// The start is the gap in the top wall, and the end is the gap in the bottom wall
+fn process_input(tile_matrix: &mut Vec<Vec<u8>>, input_string: &str) -> (Position, Position) {
+    for input_file_line in input_string.lines() {
+        // Call trim() to ensure whitespace on the ends are removed
+        let input_file_line = input_file_line.trim();
+        if input_file_line.is_empty() {
+            continue;
+        }
+
+        let line_data_vector: Vec<u8> = input_file_line
+            .bytes()
+            .map(|tile| match tile {
+                b'#' | b'.' | b'^' | b'v' | b'<' | b'>' => tile,
+                strange_value => panic!(
+                    "Could not parse valley tile! Found: {}",
+                    strange_value as char
+                ),
+            })
+            .collect();
+
+        tile_matrix.push(line_data_vector);
+    }
+
+    let get_gap_position = |row_index: usize| -> Position {
+        let column_index = tile_matrix[row_index]
+            .iter()
+            .position(|&tile| tile == b'.')
+            .unwrap_or_else(|| panic!("No gap in the wall on row {}!", row_index));
+        (row_index, column_index)
+    };
+
+    (get_gap_position(0), get_gap_position(tile_matrix.len() - 1))
+}
+
+/// Print the answers, the time taken to compute each of them, and how long each leg of the trip takes
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let valley = Valley::new(&input::read_input(input_file_name));
+
+    let part1_start = Instant::now();
+    let first_trip_minutes = valley.get_trip_arrival_minutes(1)[0];
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let arrival_minutes = valley.get_trip_arrival_minutes(3);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", first_trip_minutes);
+    println!("answer\tPart 2\t{}", arrival_minutes[2]);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+
+    println!("bars\tMinutes taken by each leg of the trip");
+    let mut leg_start_minute = 0;
+    for (leg_name, &arrival_minute) in ["There", "Back", "There again"]
+        .iter()
+        .zip(&arrival_minutes)
+    {
+        println!("0\t{}\t{}", leg_name, arrival_minute - leg_start_minute);
+        leg_start_minute = arrival_minute;
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (18, 54));
+    }
+
+    #[test]
+    fn example_legs_match_puzzle_text() {
+        let valley = Valley::new(&input::read_input("example.txt"));
+
+        // 18 minutes there, 23 back, and 13 there again
+        assert_eq!(valley.get_trip_arrival_minutes(3), vec![18, 41, 54]);
+    }
+
+    #[test]
+    fn blizzards_wrap_and_repeat() {
+        let valley = Valley::new("#.#####\n#.....#\n#>....#\n#.....#\n#...v.#\n#.....#\n#####.#\n");
+        assert_eq!(valley.period, 5);
+
+        // The right blizzard moves a tile a minute. The down blizzard comes back in at the top after minute 1
+        assert!(valley.blizzard_occupancy[1][2][2]);
+        assert!(valley.blizzard_occupancy[1][5][4]);
+        assert!(valley.blizzard_occupancy[3][2][4]);
+        assert!(valley.blizzard_occupancy[4][2][5]);
+        assert!(!valley.blizzard_occupancy[4][4][4]);
+
+        // Both are back where they started after a whole period
+        assert_eq!(valley.blizzard_occupancy[0], {
+            let mut occupancy_matrix = vec![vec![false; 7]; 7];
+            occupancy_matrix[2][1] = true;
+            occupancy_matrix[4][4] = true;
+            occupancy_matrix
+        });
+    }
+}
@@ -0,0 +1,58 @@
+/// Which way a blizzard blows. Each blizzard keeps its direction forever
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BlizzardDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl BlizzardDirection {
+    /// None for anything that isn't a blizzard
+    pub fn from_tile(tile: u8) -> Option<BlizzardDirection> {
+        match tile {
+            b'^' => Some(Self::Up),
+            b'v' => Some(Self::Down),
+            b'<' => Some(Self::Left),
+            b'>' => Some(Self::Right),
+            _ => None,
+        }
+    }
+
+    /// (row, column) step taken each minute. Rows grow downwards
+    pub fn get_step(&self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+            Self::Right => (0, 1),
+        }
+    }
+}
+
+/// Greatest common divisor, by Euclid's algorithm
+pub fn get_gcd(number_a: usize, number_b: usize) -> usize {
+    if number_b == 0 {
+        number_a
+    } else {
+        get_gcd(number_b, number_a % number_b)
+    }
+}
+
+/// Least common multiple
+pub fn get_lcm(number_a: usize, number_b: usize) -> usize {
+    number_a / get_gcd(number_a, number_b) * number_b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lcm_of_valley_sizes() {
+        assert_eq!(get_lcm(6, 4), 12);
+        assert_eq!(get_lcm(5, 5), 5);
+        assert_eq!(get_lcm(120, 25), 600);
+        assert_eq!(get_lcm(7, 1), 7);
+    }
+}
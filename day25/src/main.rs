@@ -0,0 +1,169 @@
+use aoc_common::input;
+use log::debug;
+use std::time::Instant;
+
+mod snafu;
+use snafu::{Snafu, SnafuError};
+
+const INPUT_FILENAME: &str = "example.txt";
+
+fn main() {
+    env_logger::init();
+    debug!("Starting Application!");
+
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let fuel_sum = match solve(INPUT_FILENAME) {
+        Ok(fuel_sum) => fuel_sum,
+        Err(err) => {
+            eprintln!(
+                "Could not read fuel requirements from {}: {}",
+                INPUT_FILENAME, err
+            );
+            std::process::exit(1);
+        }
+    };
+
+    println!(
+        "Part 1 | What SNAFU number do you supply to Bob's console?\nAnswer: {}",
+        fuel_sum
+    );
+}
+
+/// Sum the fuel requirements in the input file. The sum stays in SNAFU the whole way,
+/// so it's never limited by the size of an integer type
+fn solve(input_file_name: &str) -> Result<Snafu, SnafuError> {
+    let fuel_requirements = parse_fuel_requirements(&input::read_input(input_file_name))?;
+    debug!("Parsed {} fuel requirements", fuel_requirements.len());
+
+    let fuel_sum: Snafu = fuel_requirements.into_iter().sum();
+    debug!("Fuel sum in decimal: {:?}", i128::try_from(&fuel_sum));
+
+    Ok(fuel_sum)
+}
+
+/// Every non-empty line is one SNAFU number
+fn parse_fuel_requirements(input_string: &str) -> Result<Vec<Snafu>, SnafuError> {
+    input_string
+        .lines()
+        .map(|input_line| input_line.trim())
+        .filter(|input_line| !input_line.is_empty())
+        .map(|input_line| input_line.parse::<Snafu>())
+        .collect()
+}
+
+/// Print the answer and the time taken to compute it in the format read by the report crate.
+/// Day 25 only has the one part
+fn print_report(input_file_name: &str) {
+    let fuel_requirements = parse_fuel_requirements(&input::read_input(input_file_name))
+        .expect("Could not parse fuel requirements");
+
+    let part1_start = Instant::now();
+    let fuel_sum: Snafu = fuel_requirements.into_iter().sum();
+    let part1_time = part1_start.elapsed();
+
+    println!("answer\tPart 1\t{}", fuel_sum);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn example_answer() {
+        assert_eq!(
+            solve("example.txt").map(|fuel_sum| fuel_sum.to_string()),
+            Ok("2=-1=0".to_string())
+        );
+    }
+
+    #[test]
+    fn decimal_to_snafu_table() {
+        // Pairs from the tables in the puzzle text
+        for (number, snafu_str) in [
+            (0i64, "0"),
+            (1, "1"),
+            (3, "1="),
+            (4, "1-"),
+            (8, "2="),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (1747, "1=-0-2"),
+            (4890, "2=-1=0"),
+        ] {
+            assert_eq!(Snafu::from(number).to_string(), snafu_str);
+            assert_eq!(
+                i64::try_from(&snafu_str.parse::<Snafu>().unwrap()),
+                Ok(number)
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_snafu_gives_errors() {
+        assert_eq!("".parse::<Snafu>(), Err(SnafuError::Empty));
+        assert_eq!(
+            "1=3".parse::<Snafu>(),
+            Err(SnafuError::InvalidDigit('3', 2))
+        );
+        assert_eq!(
+            "2-+".parse::<Snafu>(),
+            Err(SnafuError::InvalidDigit('+', 2))
+        );
+        assert_eq!(
+            parse_fuel_requirements("1=\n12a\n"),
+            Err(SnafuError::InvalidDigit('a', 2))
+        );
+    }
+
+    #[test]
+    fn too_large_for_integer_gives_error() {
+        let max_i64 = Snafu::from(i64::MAX);
+        let one = Snafu::from(1i64);
+
+        assert_eq!(i64::try_from(&max_i64), Ok(i64::MAX));
+        assert_eq!(
+            i64::try_from(&(&max_i64 + &one)),
+            Err(SnafuError::OutOfRange)
+        );
+        assert_eq!(i128::try_from(&(&max_i64 + &one)), Ok(i64::MAX as i128 + 1));
+
+        let max_i128 = Snafu::from(i128::MAX);
+        assert_eq!(
+            i128::try_from(&(&max_i128 + &one)),
+            Err(SnafuError::OutOfRange)
+        );
+        assert_eq!(i128::try_from(&Snafu::from(i128::MIN)), Ok(i128::MIN));
+    }
+
+    #[test]
+    fn leading_zeros_are_dropped() {
+        assert_eq!("0012".parse::<Snafu>().unwrap().to_string(), "12");
+        assert_eq!("000".parse::<Snafu>().unwrap(), Snafu::from(0i64));
+    }
+
+    proptest! {
+        #[test]
+        fn integer_round_trips_through_snafu(number in any::<i128>()) {
+            let snafu = Snafu::from(number);
+            prop_assert_eq!(i128::try_from(&snafu), Ok(number));
+            prop_assert_eq!(snafu.to_string().parse::<Snafu>(), Ok(snafu));
+        }
+
+        #[test]
+        fn snafu_addition_matches_integer_addition(number_a in any::<i64>(), number_b in any::<i64>()) {
+            let snafu_sum = &Snafu::from(number_a) + &Snafu::from(number_b);
+            prop_assert_eq!(i128::try_from(&snafu_sum), Ok(number_a as i128 + number_b as i128));
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::{fmt, iter::Sum, ops::Add, str::FromStr};
+
+/// A number in SNAFU: balanced base 5, where each place is worth five times the one to its right,
+/// and the digits are 2, 1, 0, - (minus one) and = (minus two)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Snafu {
+    /// Digit values from -2 to 2, least significant first. Never has zeros at the end, so 0 has no digits at all
+    digits: Vec<i8>,
+}
+
+impl Snafu {
+    /// Drop the most significant zeros, so every number has exactly one representation
+    fn from_digits(mut digits: Vec<i8>) -> Snafu {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Snafu { digits }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SnafuError {
+    /// Nothing to parse, like a blank line
+    Empty,
+    /// Anything other than 2, 1, 0, - or =. Holds the character and its byte offset
+    InvalidDigit(char, usize),
+    /// The number doesn't fit in the integer type it's being converted to
+    OutOfRange,
+}
+
+impl fmt::Display for SnafuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "SNAFU number has no digits"),
+            Self::InvalidDigit(character, offset) => {
+                write!(
+                    f,
+                    "invalid SNAFU digit '{}' at offset {}",
+                    character, offset
+                )
+            }
+            Self::OutOfRange => write!(f, "SNAFU number is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for SnafuError {}
+
+impl FromStr for Snafu {
+    type Err = SnafuError;
+
+    /// Example: "1=-0-2" is 1747
+    fn from_str(snafu_str: &str) -> Result<Self, Self::Err> {
+        let snafu_str = snafu_str.trim();
+        if snafu_str.is_empty() {
+            return Err(SnafuError::Empty);
+        }
+
+        let mut digits: Vec<i8> = snafu_str
+            .char_indices()
+            .map(|(offset, digit_char)| match digit_char {
+                '2' => Ok(2),
+                '1' => Ok(1),
+                '0' => Ok(0),
+                '-' => Ok(-1),
+                '=' => Ok(-2),
+                strange_value => Err(SnafuError::InvalidDigit(strange_value, offset)),
+            })
+            .collect::<Result<Vec<i8>, SnafuError>>()?;
+        digits.reverse();
+
+        Ok(Snafu::from_digits(digits))
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+
+        for &digit in self.digits.iter().rev() {
+            let digit_char = match digit {
+                2 => '2',
+                1 => '1',
+                0 => '0',
+                -1 => '-',
+                -2 => '=',
+                strange_value => unreachable!("SNAFU digit out of range: {}", strange_value),
+            };
+            write!(f, "{}", digit_char)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<i128> for Snafu {
+    /// Take off a base 5 digit at a time. A remainder of 3 or 4 is written as -2 or -1,
+    /// with one carried into the next place up
+    fn from(number: i128) -> Self {
+        let mut digits: Vec<i8> = Vec::new();
+        let mut remaining_number = number;
+
+        while remaining_number != 0 {
+            let remainder = remaining_number.rem_euclid(5);
+            remaining_number = remaining_number.div_euclid(5);
+
+            if remainder > 2 {
+                digits.push((remainder - 5) as i8);
+                remaining_number += 1;
+            } else {
+                digits.push(remainder as i8);
+            }
+        }
+
+        Snafu::from_digits(digits)
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(number: i64) -> Self {
+        Snafu::from(number as i128)
+    }
+}
+
+impl TryFrom<&Snafu> for i128 {
+    type Error = SnafuError;
+
+    /// Most significant digit first, multiplying by 5 each place. That's done as 4 times, plus the digit,
+    /// plus once more, because times 5 on its own can overflow just before the digit brings it back in range.
+    /// i128::MIN ends in a 2, for one
+    fn try_from(snafu: &Snafu) -> Result<Self, Self::Error> {
+        snafu.digits.iter().rev().try_fold(0i128, |number, &digit| {
+            number
+                .checked_mul(4)
+                .and_then(|partial_number| partial_number.checked_add(digit as i128))
+                .and_then(|partial_number| partial_number.checked_add(number))
+                .ok_or(SnafuError::OutOfRange)
+        })
+    }
+}
+
+impl TryFrom<&Snafu> for i64 {
+    type Error = SnafuError;
+
+    fn try_from(snafu: &Snafu) -> Result<Self, Self::Error> {
+        i64::try_from(i128::try_from(snafu)?).map_err(|_| SnafuError::OutOfRange)
+    }
+}
+
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    /// Add place by place, like long addition. A place sums to between -5 and 5 with the carry,
+    /// which is balanced back into -2 to 2 by carrying -1, 0 or 1 into the next place
+    fn add(self, other: &Snafu) -> Snafu {
+        let place_count = self.digits.len().max(other.digits.len());
+        let mut digits: Vec<i8> = Vec::with_capacity(place_count + 1);
+        let mut carry: i8 = 0;
+
+        for place in 0..place_count {
+            let place_sum = self.digits.get(place).unwrap_or(&0)
+                + other.digits.get(place).unwrap_or(&0)
+                + carry;
+
+            carry = match place_sum {
+                3.. => 1,
+                ..=-3 => -1,
+                _ => 0,
+            };
+            digits.push(place_sum - carry * 5);
+        }
+        digits.push(carry);
+
+        Snafu::from_digits(digits)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        &self + &other
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(snafus: I) -> Self {
+        snafus.fold(Snafu::default(), |total, snafu| total + snafu)
+    }
+}
@@ -2,19 +2,48 @@ use std::{
     collections::HashSet,
     fs::File,
     io::{BufRead, BufReader},
+    time::Instant,
 };
 
 use log::{debug, trace};
 
+const INPUT_FILENAME: &str = "input.txt";
+
 fn main() {
     //Set $RUST_LOG='trace' or 'debug' depending on what you want to see
     // https://docs.rs/env_logger/latest/env_logger/#enabling-logging
     env_logger::init();
 
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
     trace!("Starting Application!");
     debug!("Debug test!");
 
-    let input_file_lines = BufReader::new(File::open("input.txt").expect("Could not open input file!")).lines();
+    let (sum_of_priorities, sum_of_group_priorities) = solve(INPUT_FILENAME);
+
+    println!("Part 1 - Sum of Prioritues: {}", sum_of_priorities);
+    println!(
+        "Part 2 - Sum of Group Priorities: {}",
+        sum_of_group_priorities
+    );
+}
+
+/// Go through the rucksacks in the input file, and return the answers to both parts
+fn solve(input_file_name: &str) -> (i32, i32) {
+    (
+        get_sum_of_priorities(input_file_name),
+        get_sum_of_group_priorities(input_file_name),
+    )
+}
+
+/// Part 1 - Sum of the priorities of the item common to both compartments of each rucksack
+fn get_sum_of_priorities(input_file_name: &str) -> i32 {
+    let input_file_lines =
+        BufReader::new(File::open(input_file_name).expect("Could not open input file!")).lines();
 
     let mut sum_of_priorities = 0;
 
@@ -68,13 +97,18 @@ fn main() {
         }
     } // for loop
 
+    sum_of_priorities
+}
+
+/// Part 2 - Sum of the priorities of the badge item common to each group of three rucksacks
+fn get_sum_of_group_priorities(input_file_name: &str) -> i32 {
     debug!("Beginning Part 2 Solution");
 
     /* Below code strictly for Part 2 Solution */
     let mut sum_of_group_priorities = 0;
 
     let mut input_file_reader =
-        BufReader::new(File::open("input.txt").expect("Could not open input file!"));
+        BufReader::new(File::open(input_file_name).expect("Could not open input file!"));
 
     loop {
         let mut first_line = String::new();
@@ -123,7 +157,7 @@ fn main() {
 
         let mut common_items: HashSet<char> = HashSet::new();
         for common_item in first_line_set.intersection(&second_line_set) {
-            common_items.insert(common_item.clone());
+            common_items.insert(*common_item);
         }
 
         //Read in items for third line
@@ -136,7 +170,7 @@ fn main() {
         // From the examples, its just one item
         let mut common_items_all: HashSet<char> = HashSet::new();
         for common_item in third_line_set.intersection(&common_items) {
-            common_items_all.insert(common_item.clone());
+            common_items_all.insert(*common_item);
         }
 
         for item in common_items_all {
@@ -144,11 +178,23 @@ fn main() {
         }
     }
 
-    println!("Part 1 - Sum of Prioritues: {}", sum_of_priorities);
-    println!(
-        "Part 2 - Sum of Group Priorities: {}",
-        sum_of_group_priorities
-    );
+    sum_of_group_priorities
+}
+
+/// Print the answers, and the time taken to compute each of them, in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let part1_start = Instant::now();
+    let sum_of_priorities = get_sum_of_priorities(input_file_name);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let sum_of_group_priorities = get_sum_of_group_priorities(input_file_name);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", sum_of_priorities);
+    println!("answer\tPart 2\t{}", sum_of_group_priorities);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
 }
 
 fn get_priority(input: char) -> i32 {
@@ -159,11 +205,70 @@ fn get_priority(input: char) -> i32 {
 
     //Handle lowercase
     if input.is_lowercase() {
-        let diff = (input as u8) - ('a' as u8);
-        return lowercase_base_priority + (diff as i32);
+        let diff = (input as u8) - b'a';
+        lowercase_base_priority + (diff as i32)
     } else {
         // Handle uppercase
-        let diff = (input as u8) - ('A' as u8);
-        return uppercase_base_priority + (diff as i32);
+        let diff = (input as u8) - b'A';
+        uppercase_base_priority + (diff as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (157, 70));
+    }
+
+    #[test]
+    fn get_priority_of_example_items() {
+        // Common items from the puzzle example
+        assert_eq!(get_priority('p'), 16);
+        assert_eq!(get_priority('L'), 38);
+        assert_eq!(get_priority('P'), 42);
+        assert_eq!(get_priority('v'), 22);
+        assert_eq!(get_priority('t'), 20);
+        assert_eq!(get_priority('s'), 19);
+    }
+
+    #[test]
+    fn get_priority_range_bounds() {
+        assert_eq!(get_priority('a'), 1);
+        assert_eq!(get_priority('z'), 26);
+        assert_eq!(get_priority('A'), 27);
+        assert_eq!(get_priority('Z'), 52);
+    }
+
+    proptest! {
+        #[test]
+        fn lowercase_items_have_priorities_1_to_26(item in proptest::char::range('a', 'z')) {
+            let priority = get_priority(item);
+            prop_assert!((1..=26).contains(&priority));
+            prop_assert_eq!(priority, (item as i32) - ('a' as i32) + 1);
+        }
+
+        #[test]
+        fn uppercase_items_have_priorities_27_to_52(item in proptest::char::range('A', 'Z')) {
+            let priority = get_priority(item);
+            prop_assert!((27..=52).contains(&priority));
+            prop_assert_eq!(get_priority(item.to_ascii_lowercase()) + 26, priority);
+        }
+
+        #[test]
+        fn different_items_have_different_priorities(
+            first_item in "[a-zA-Z]",
+            second_item in "[a-zA-Z]",
+        ) {
+            let first_item = first_item.chars().next().unwrap();
+            let second_item = second_item.chars().next().unwrap();
+            prop_assert_eq!(
+                get_priority(first_item) == get_priority(second_item),
+                first_item == second_item
+            );
+        }
     }
 }
@@ -1,17 +1,40 @@
 use std::{
-    collections::HashSet,
     fs::File,
     io::{BufRead, BufReader},
+    time::Instant,
 };
 
+use aoc_common::intervals;
 use log::{debug, trace};
 
+const INPUT_FILENAME: &str = "input.txt";
+
 fn main() {
     //Set env var RUST_LOG = "trace" or "debug"
     env_logger::init();
 
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (contained_pairs_count, overlap_pairs_count) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | In how many assignment pairs does one range fully contain the other?\nAnswer: {}",
+        contained_pairs_count
+    );
+    println!(
+        "Part 2 | In how many assignment pairs do the ranges overlap?\nAnswer: {}",
+        overlap_pairs_count
+    );
+}
+
+/// Count the fully contained and the overlapping assignment pairs in the input file
+fn solve(input_file_name: &str) -> (i32, i32) {
     let input_file_reader =
-        BufReader::new(File::open("input.txt").expect("Could not open input file!"));
+        BufReader::new(File::open(input_file_name).expect("Could not open input file!"));
 
     let mut contained_pairs_count = 0;
     let mut overlap_pairs_count = 0;
@@ -35,34 +58,20 @@ fn main() {
             // If true increment count and go on to next line
             // NOTE: If one pair contains the other, it automatically implies overlap
             // So we will not specifically check overlap for those pairs
-            if elf1_range_start <= elf2_range_start && elf1_range_end >= elf2_range_end {
-                debug!("Elf 1 range contains Elf 2 range!");
-                contained_pairs_count += 1;
-                overlap_pairs_count += 1;
-                continue;
-            }
-
-            if elf2_range_start <= elf1_range_start && elf2_range_end >= elf1_range_end {
-                debug!("Elf 2 range contains Elf 1 range!");
+            if is_either_range_contained(
+                (elf1_range_start, elf1_range_end),
+                (elf2_range_start, elf2_range_end),
+            ) {
                 contained_pairs_count += 1;
                 overlap_pairs_count += 1;
                 continue;
             }
 
             // Check overlap for pairs where one does not contain the other
-            // The problem can be reduced to one where we find one or more common elements in two sequences
-
-            let mut elf1_range_set: HashSet<i32> = HashSet::new();
-            for i in elf1_range_start..=elf1_range_end {
-                elf1_range_set.insert(i);
-            }
-
-            let mut elf2_range_set: HashSet<i32> = HashSet::new();
-            for i in elf2_range_start..=elf2_range_end {
-                elf2_range_set.insert(i);
-            }
-
-            let set_intersection_count = elf1_range_set.intersection(&elf2_range_set).count();
+            let set_intersection_count = get_overlap_size(
+                (elf1_range_start, elf1_range_end),
+                (elf2_range_start, elf2_range_end),
+            );
 
             if set_intersection_count > 0 {
                 debug!(
@@ -76,14 +85,7 @@ fn main() {
         }
     }
 
-    println!(
-        "Part 1 | In how many assignment pairs does one range fully contain the other?\nAnswer: {}",
-        contained_pairs_count
-    );
-    println!(
-        "Part 2 | In how many assignment pairs do the ranges overlap?\nAnswer: {}",
-        overlap_pairs_count
-    );
+    (contained_pairs_count, overlap_pairs_count)
 }
 
 fn get_assignment_range(range_str: &str) -> (i32, i32) {
@@ -92,5 +94,123 @@ fn get_assignment_range(range_str: &str) -> (i32, i32) {
     let range_start = range_parts[0].parse::<i32>().unwrap();
     let range_end = range_parts[1].parse::<i32>().unwrap();
 
-    return (range_start, range_end);
+    (range_start, range_end)
+}
+
+/// Check if either of the two ranges fully contains the other
+fn is_either_range_contained(elf1_range: (i32, i32), elf2_range: (i32, i32)) -> bool {
+    let (elf1_range_start, elf1_range_end) = elf1_range;
+    let (elf2_range_start, elf2_range_end) = elf2_range;
+
+    if elf1_range_start <= elf2_range_start && elf1_range_end >= elf2_range_end {
+        debug!("Elf 1 range contains Elf 2 range!");
+        return true;
+    }
+
+    if elf2_range_start <= elf1_range_start && elf2_range_end >= elf1_range_end {
+        debug!("Elf 2 range contains Elf 1 range!");
+        return true;
+    }
+
+    false
+}
+
+/// Get the number of sections that are common to both ranges
+fn get_overlap_size(elf1_range: (i32, i32), elf2_range: (i32, i32)) -> usize {
+    // The ranges are merged like day 15 merges sensor coverage, rather than listing every section in a set.
+    // Sections covered by both ranges are only counted once in the merged range
+    intervals::get_overlap_size(
+        (i64::from(elf1_range.0), i64::from(elf1_range.1)),
+        (i64::from(elf2_range.0), i64::from(elf2_range.1)),
+    )
+}
+
+/// Print the answers, and the time taken to compute them, in the format read by the report crate.
+/// Both parts are computed in the same pass, so they share a single timing
+fn print_report(input_file_name: &str) {
+    let solve_start = Instant::now();
+    let (part1_answer, part2_answer) = solve(input_file_name);
+    let solve_time = solve_start.elapsed();
+
+    println!("answer\tPart 1\t{}", part1_answer);
+    println!("answer\tPart 2\t{}", part2_answer);
+    println!("timing\tBoth parts\t{}", solve_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (2, 4));
+    }
+
+    #[test]
+    fn get_assignment_range_parses_both_ends() {
+        assert_eq!(get_assignment_range("2-4"), (2, 4));
+        assert_eq!(get_assignment_range("6-6"), (6, 6));
+        assert_eq!(get_assignment_range("14-50"), (14, 50));
+    }
+
+    /// A section assignment, like the ones in the puzzle input. Start is never after the end
+    fn any_assignment_range() -> impl Strategy<Value = (i32, i32)> {
+        (1..=99, 0..=30)
+            .prop_map(|(range_start, range_length)| (range_start, range_start + range_length))
+    }
+
+    proptest! {
+        #[test]
+        fn containment_implies_overlap(
+            elf1_range in any_assignment_range(),
+            elf2_range in any_assignment_range(),
+        ) {
+            if is_either_range_contained(elf1_range, elf2_range) {
+                prop_assert!(get_overlap_size(elf1_range, elf2_range) > 0);
+            }
+        }
+
+        #[test]
+        fn contained_range_overlaps_by_its_own_size(
+            elf1_range in any_assignment_range(),
+            elf2_range in any_assignment_range(),
+        ) {
+            if is_either_range_contained(elf1_range, elf2_range) {
+                let smaller_range_size =
+                    ((elf1_range.1 - elf1_range.0).min(elf2_range.1 - elf2_range.0) + 1) as usize;
+                prop_assert_eq!(get_overlap_size(elf1_range, elf2_range), smaller_range_size);
+            }
+        }
+
+        #[test]
+        fn checks_are_symmetric(
+            elf1_range in any_assignment_range(),
+            elf2_range in any_assignment_range(),
+        ) {
+            prop_assert_eq!(
+                is_either_range_contained(elf1_range, elf2_range),
+                is_either_range_contained(elf2_range, elf1_range)
+            );
+            prop_assert_eq!(
+                get_overlap_size(elf1_range, elf2_range),
+                get_overlap_size(elf2_range, elf1_range)
+            );
+        }
+
+        #[test]
+        fn overlap_matches_endpoint_comparison(
+            elf1_range in any_assignment_range(),
+            elf2_range in any_assignment_range(),
+        ) {
+            let ends_cross = elf1_range.0 <= elf2_range.1 && elf2_range.0 <= elf1_range.1;
+            prop_assert_eq!(get_overlap_size(elf1_range, elf2_range) > 0, ends_cross);
+        }
+
+        #[test]
+        fn assignment_range_round_trips(assignment_range in any_assignment_range()) {
+            let range_str = format!("{}-{}", assignment_range.0, assignment_range.1);
+            prop_assert_eq!(get_assignment_range(&range_str), assignment_range);
+        }
+    }
 }
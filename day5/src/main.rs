@@ -2,6 +2,7 @@ use std::{
     collections::LinkedList,
     fs::File,
     io::{BufRead, BufReader},
+    time::Instant,
 };
 
 use log::{debug, trace};
@@ -14,8 +15,22 @@ fn main() {
     //Set RUST_LOG = "debug" or "trace"
     env_logger::init();
 
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
     debug!("Starting Application!");
 
+    let (top_crates_string, part2_top_crates_string) = solve(INPUT_FILENAME);
+
+    println!("Part 1 | After the rearrangement procedure completes, what crate ends up on top of each stack?\nAnswer: {}",top_crates_string);
+    println!("Part 2 | After the rearrangement procedure completes, what crate ends up on top of each stack?\nAnswer: {}",part2_top_crates_string);
+}
+
+/// Rearrange the stacks in the input file with both crane models, and return the top crates of each
+fn solve(input_file_name: &str) -> (String, String) {
     // This problem can be interpreted as follows
     // There are N number of stacks, each containing a certain number of elements
     // this is followed by a list of operations where number of elements are transferred from one stack to another
@@ -28,7 +43,7 @@ fn main() {
     // Note: The example input contains three stacks. But the puzzle input contains nine.
     // To make a program that can solve any problem of this type, we will need to count the number of stacks.
 
-    let stacks_count = utils::get_num_of_stacks(INPUT_FILENAME);
+    let stacks_count = utils::get_num_of_stacks(input_file_name);
 
     debug!("No. of stacks: {}", stacks_count);
 
@@ -51,15 +66,15 @@ fn main() {
         stacks_vector.len()
     );
 
-    utils::insert_crates_into_stacks(INPUT_FILENAME, &mut stacks_vector);
-    utils::insert_crates_into_stacks(INPUT_FILENAME, &mut part2_stacks_vector);
+    utils::insert_crates_into_stacks(input_file_name, &mut stacks_vector);
+    utils::insert_crates_into_stacks(input_file_name, &mut part2_stacks_vector);
 
     trace!("Initial state of stacks: {:?}", stacks_vector);
 
     // We have processed the input and have the data about the initial state of the stacks ! Now time to process the move procedures
 
     let input_file_lines =
-        BufReader::new(File::open(INPUT_FILENAME).expect("Could not open input file!")).lines();
+        BufReader::new(File::open(input_file_name).expect("Could not open input file!")).lines();
 
     for input_file_line in input_file_lines {
         if let Ok(current_line) = input_file_line {
@@ -110,15 +125,39 @@ fn main() {
     // Prepare the answer string by getting the top crate in all stacks
     let mut top_crates_string = String::new();
     for stack in stacks_vector {
-        top_crates_string.push(stack.back().unwrap().clone());
+        top_crates_string.push(*stack.back().unwrap());
     }
 
     // Prepare the answer string for Part 2 by getting the top crate in all stacks
     let mut part2_top_crates_string = String::new();
     for stack in part2_stacks_vector {
-        part2_top_crates_string.push(stack.back().unwrap().clone());
+        part2_top_crates_string.push(*stack.back().unwrap());
     }
 
-    println!("Part 1 | After the rearrangement procedure completes, what crate ends up on top of each stack?\nAnswer: {}",top_crates_string);
-    println!("Part 2 | After the rearrangement procedure completes, what crate ends up on top of each stack?\nAnswer: {}",part2_top_crates_string);
+    (top_crates_string, part2_top_crates_string)
+}
+
+/// Print the answers, and the time taken to compute them, in the format read by the report crate.
+/// Both parts are computed in the same pass, so they share a single timing
+fn print_report(input_file_name: &str) {
+    let solve_start = Instant::now();
+    let (part1_answer, part2_answer) = solve(input_file_name);
+    let solve_time = solve_start.elapsed();
+
+    println!("answer\tPart 1\t{}", part1_answer);
+    println!("answer\tPart 2\t{}", part2_answer);
+    println!("timing\tBoth parts\t{}", solve_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(
+            solve("example.txt"),
+            (String::from("CMZ"), String::from("MCD"))
+        );
+    }
 }
@@ -29,10 +29,10 @@ pub fn get_num_of_stacks(input_file_name: &str) -> i32 {
         }
     }
 
-    return 0;
+    0
 }
 
-pub fn insert_crates_into_stacks(input_file_name: &str, stacks_vector: &mut Vec<LinkedList<char>>) {
+pub fn insert_crates_into_stacks(input_file_name: &str, stacks_vector: &mut [LinkedList<char>]) {
     let input_file_lines =
         BufReader::new(File::open(input_file_name).expect("Could not open input file!")).lines();
 
@@ -60,9 +60,7 @@ pub fn insert_crates_into_stacks(input_file_name: &str, stacks_vector: &mut Vec<
 
             let mut current_crate_index = 1;
             let mut current_stack_index = 1;
-            let mut chars_and_indices = current_line.char_indices();
-
-            while let Some((index, crate_name)) = chars_and_indices.next() {
+            for (index, crate_name) in current_line.char_indices() {
                 if index != current_crate_index {
                     continue;
                 }
@@ -91,7 +89,7 @@ pub fn insert_crates_into_stacks(input_file_name: &str, stacks_vector: &mut Vec<
 }
 
 pub fn move_crates(
-    stacks_vector: &mut Vec<LinkedList<char>>,
+    stacks_vector: &mut [LinkedList<char>],
     source_stack_index: usize,
     dest_stack_index: usize,
     move_count: usize,
@@ -123,7 +121,7 @@ pub fn move_crates(
 
 /// To help move multiple crates at once with the CrateMover 9001!
 pub fn move_crates_part2(
-    stacks_vector: &mut Vec<LinkedList<char>>,
+    stacks_vector: &mut [LinkedList<char>],
     source_stack_index: usize,
     dest_stack_index: usize,
     move_count: usize,
@@ -165,18 +163,44 @@ pub fn move_crates_part2(
  * 3) Destination Stack
  */
 pub fn interpret_move_procedure(move_procedure: &str) -> (usize, usize, usize) {
-    let move_count: usize;
-    let source_stack: usize;
-    let dest_stack: usize;
-
     // Example Input move 5 from 5 to 9
     // Split on whitespace to get all the parts
 
     let procedure_parts: Vec<&str> = move_procedure.split_whitespace().collect();
 
-    move_count = procedure_parts.get(1).unwrap().parse::<i32>().unwrap() as usize;
-    source_stack = procedure_parts.get(3).unwrap().parse::<i32>().unwrap() as usize;
-    dest_stack = procedure_parts.get(5).unwrap().parse::<i32>().unwrap() as usize;
+    let move_count = procedure_parts.get(1).unwrap().parse::<i32>().unwrap() as usize;
+    let source_stack = procedure_parts.get(3).unwrap().parse::<i32>().unwrap() as usize;
+    let dest_stack = procedure_parts.get(5).unwrap().parse::<i32>().unwrap() as usize;
+
+    (move_count, source_stack, dest_stack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    return (move_count, source_stack, dest_stack);
+    #[test]
+    fn interpret_move_procedure_reads_count_source_and_dest() {
+        assert_eq!(interpret_move_procedure("move 1 from 2 to 1"), (1, 2, 1));
+        assert_eq!(interpret_move_procedure("move 3 from 1 to 3"), (3, 1, 3));
+        assert_eq!(interpret_move_procedure("move 15 from 5 to 9"), (15, 5, 9));
+    }
+
+    #[test]
+    fn interpret_move_procedure_ignores_trailing_carriage_return() {
+        assert_eq!(interpret_move_procedure("move 2 from 2 to 1\r"), (2, 2, 1));
+    }
+
+    #[test]
+    fn move_crates_reverses_order_but_part2_keeps_it() {
+        let mut stacks_vector: Vec<LinkedList<char>> =
+            vec![LinkedList::from(['Z', 'N', 'D']), LinkedList::new()];
+        move_crates(&mut stacks_vector, 1, 2, 3);
+        assert_eq!(stacks_vector[1], LinkedList::from(['D', 'N', 'Z']));
+
+        let mut stacks_vector: Vec<LinkedList<char>> =
+            vec![LinkedList::from(['Z', 'N', 'D']), LinkedList::new()];
+        move_crates_part2(&mut stacks_vector, 1, 2, 3);
+        assert_eq!(stacks_vector[1], LinkedList::from(['Z', 'N', 'D']));
+    }
 }
@@ -1,6 +1,7 @@
+use aoc_common::input;
 use std::{
     collections::{HashSet, VecDeque},
-    fs::read_to_string,
+    time::Instant,
 };
 
 use log::{debug, trace};
@@ -10,15 +11,35 @@ const INPUT_FILENAME: &str = "example.txt";
 fn main() {
     env_logger::init();
 
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
     debug!("Starting Application!");
 
-    let input_string = read_to_string(INPUT_FILENAME).expect("Unable to read input file!");
+    let (start_of_packet_marker, start_of_message_marker) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | How many characters need to be processed before the first start-of-packet marker is detected?\nAnswer: {}",
+        start_of_packet_marker
+    );
+    println!(
+        "Part 2 | How many characters need to be processed before the first start-of-message marker is detected?\nAnswer: {}",
+        start_of_message_marker
+    );
+}
+
+/// Find the start-of-packet and start-of-message markers in the datastream in the input file
+fn solve(input_file_name: &str) -> (i32, i32) {
+    let input_string = input::read_input(input_file_name);
 
     // To solve this problem, we need a moving window of size 4 or 14. A queue data structure would be useful
     // Let's use a VecDeque for this from std::Collections
     // push_back() to enqueue. pop_front() to dequeue
 
-    trace!("Input String => {}", input_string);
+    trace!("Input String => {}", &*input_string);
 
     debug!("Starting Part 1 Solution code now");
 
@@ -31,23 +52,15 @@ fn main() {
     // For Part 1, pass window size of 4 to the method .
     let start_of_packet_marker = get_marker_index::<4>(&input_string);
 
-    println!(
-        "Part 1 | How many characters need to be processed before the first start-of-packet marker is detected?\nAnswer: {}",
-        start_of_packet_marker
-    );
-
     debug!("Starting Part 2 Solution code now");
 
     // For part 2, pass window size of 14 to the method
     let start_of_message_marker = get_marker_index::<14>(&input_string);
 
-    println!(
-        "Part 2 | How many characters need to be processed before the first start-of-message marker is detected?\nAnswer: {}",
-        start_of_message_marker
-    );
+    (start_of_packet_marker, start_of_message_marker)
 }
 
-fn get_marker_index<const WINDOW_SIZE: i32>(input_string: &String) -> i32 {
+fn get_marker_index<const WINDOW_SIZE: i32>(input_string: &str) -> i32 {
     // This is the value we will return.
     let mut marker_index: i32 = 0;
 
@@ -56,8 +69,7 @@ fn get_marker_index<const WINDOW_SIZE: i32>(input_string: &String) -> i32 {
 
     // Time to process the input!
 
-    let mut input_char_indices = input_string.char_indices();
-    while let Some((char_index, packet_char)) = input_char_indices.next() {
+    for (char_index, packet_char) in input_string.char_indices() {
         debug!("Current Packet Window Contents: {:?}", packet_window);
         trace!("Current char: {}", packet_char);
 
@@ -75,7 +87,7 @@ fn get_marker_index<const WINDOW_SIZE: i32>(input_string: &String) -> i32 {
                 packet_window,
                 WINDOW_SIZE
             );
-            
+
             if let Some(dequeued_char) = packet_window.pop_front() {
                 trace!("De-queued char {} from the queue", dequeued_char);
             } else {
@@ -96,9 +108,9 @@ fn get_marker_index<const WINDOW_SIZE: i32>(input_string: &String) -> i32 {
             marker_index = (char_index + 1) as i32;
             break;
         }
-    } //while loop
+    } //for loop
 
-    return marker_index;
+    marker_index
 }
 
 fn all_chars_are_unique(packet_window: &VecDeque<char>) -> bool {
@@ -108,12 +120,58 @@ fn all_chars_are_unique(packet_window: &VecDeque<char>) -> bool {
     let mut char_set: HashSet<char> = HashSet::new();
 
     for window_char in packet_window {
-        char_set.insert(window_char.clone());
+        char_set.insert(*window_char);
+    }
+
+    char_set.len() == packet_window.len()
+}
+
+/// Print the answers, and the time taken to compute each of them, in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let input_string = input::read_input(input_file_name);
+
+    let part1_start = Instant::now();
+    let start_of_packet_marker = get_marker_index::<4>(&input_string);
+    let part1_time = part1_start.elapsed();
+
+    let part2_start = Instant::now();
+    let start_of_message_marker = get_marker_index::<14>(&input_string);
+    let part2_time = part2_start.elapsed();
+
+    println!("answer\tPart 1\t{}", start_of_packet_marker);
+    println!("answer\tPart 2\t{}", start_of_message_marker);
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (10, 29));
     }
 
-    if char_set.len() == packet_window.len() {
-        return true;
+    #[test]
+    fn get_marker_index_for_all_puzzle_examples() {
+        let puzzle_examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+
+        for (datastream, start_of_packet, start_of_message) in puzzle_examples {
+            assert_eq!(get_marker_index::<4>(datastream), start_of_packet);
+            assert_eq!(get_marker_index::<14>(datastream), start_of_message);
+        }
     }
 
-    return false;
+    #[test]
+    fn all_chars_are_unique_detects_repeats() {
+        assert!(all_chars_are_unique(&VecDeque::from(['a', 'b', 'c', 'd'])));
+        assert!(!all_chars_are_unique(&VecDeque::from(['a', 'b', 'c', 'a'])));
+    }
 }
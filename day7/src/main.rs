@@ -1,5 +1,6 @@
+use aoc_common::input;
 use log::{debug, trace, warn};
-use std::{cell::RefCell, fs::read_to_string, rc::Rc, str::FromStr};
+use std::{cell::RefCell, rc::Rc, time::Instant};
 
 mod utils;
 use utils::{get_line_type, InputLineType, PuzzleDir, PuzzleFile};
@@ -9,13 +10,31 @@ const INPUT_FILENAME: &str = "puzzle.txt";
 fn main() {
     env_logger::init();
 
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
     debug!("Starting application");
 
-    let input_file_lines: Vec<String> = read_to_string(INPUT_FILENAME)
-        .expect("Could not read input file!")
-        .split('\n')
-        .map(|line| String::from(line))
-        .collect();
+    let (part1_answer, part2_answer) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | What is the sum of the total sizes of those directories with a total size of at most 100000?\nAnswer: {}",
+        part1_answer
+    );
+    println!(
+        "Part 2 | What is the total size of the smallest directory to be deleted to create 30_000_000 of free space?\nAnswer: {}",
+        part2_answer
+    );
+}
+
+/// Rebuild the directory tree from the terminal output in the input file, and return the answers to both parts
+fn solve(input_file_name: &str) -> (usize, usize) {
+    // Every line is a &str slice borrowed from the single input buffer. No per-line Strings are allocated.
+    let input_buffer = input::read_input(input_file_name);
+    let input_file_lines: Vec<&str> = input_buffer.lines().collect();
 
     debug!("Read in {} lines from input file", input_file_lines.len());
 
@@ -101,7 +120,7 @@ fn main() {
     // Double de-structuring in filter() - https://doc.rust-lang.org/core/iter/trait.Iterator.html#examples-14
     let part1_answer: usize = dir_size_vec
         .iter()
-        .filter(|&&x| x <= 100_000 as usize)
+        .filter(|&&x| x <= 100_000)
         .sum();
 
     // Solve for Part 2
@@ -122,21 +141,14 @@ fn main() {
             }
         });
 
-    println!(
-        "Part 1 | What is the sum of the total sizes of those directories with a total size of at most 100000?\nAnswer: {}",
-        part1_answer
-    );
-    println!(
-        "Part 2 | What is the total size of the smallest directory to be deleted to create 30_000_000 of free space?\nAnswer: {}",
-        part2_answer
-    );
+    (part1_answer, part2_answer)
 }
 
 // Returned value is the next value to be processed
-fn process_input(
-    input_file_lines: &Vec<String>,
+fn process_input<'a>(
+    input_file_lines: &[&'a str],
     input_line_index: usize,
-    this_dir: Rc<RefCell<PuzzleDir>>,
+    this_dir: Rc<RefCell<PuzzleDir<'a>>>,
 ) -> usize {
     // Process the ls command entries
     // Check for safety anyway
@@ -145,10 +157,9 @@ fn process_input(
     let mut current_line_index = input_line_index;
 
     while current_line_index < input_file_lines.len() {
-        let current_line = input_file_lines
+        let current_line = *input_file_lines
             .get(current_line_index)
             .expect("Could not fetch input line string from vector");
-        let current_line_parts: Vec<&str> = current_line.split_whitespace().collect();
 
         trace!("We are in directory: {}", this_dir.borrow().name);
         trace!(
@@ -177,8 +188,9 @@ fn process_input(
             // Is this subdir? aka "dir abcd"
             InputLineType::LsOutputDir => {
                 let new_subdir_ref = Rc::new(RefCell::new(PuzzleDir::new(
-                    current_line_parts
-                        .get(1)
+                    current_line
+                        .split_whitespace()
+                        .nth(1)
                         .expect("Could not get subdir name"),
                 )));
                 trace!(
@@ -194,11 +206,13 @@ fn process_input(
 
             // Is this an entry for a file? aka "123456 file.txt"
             InputLineType::LsOutputFile => {
-                let new_file_name = *current_line_parts
-                    .get(1)
+                let new_file_name = current_line
+                    .split_whitespace()
+                    .nth(1)
                     .expect("Could not get the file name");
-                let new_file_size = current_line_parts
-                    .get(0)
+                let new_file_size = current_line
+                    .split_whitespace()
+                    .next()
                     .expect("Could not get the file size")
                     .parse::<usize>()
                     .expect("Could not parse the file size");
@@ -207,7 +221,7 @@ fn process_input(
 
                 trace!(
                     "Adding new file {} to dir {}",
-                    new_file_name,
+                    new_file.name,
                     this_dir.borrow().name
                 );
 
@@ -228,12 +242,10 @@ fn process_input(
             // so no need to worry about a dir not existing
             InputLineType::CommandCdIntoDir => {
                 // Get the name of the directory
-                let subdir_name = String::from_str(
-                    *current_line_parts
-                        .get(2)
-                        .expect("Could not get subdir name"),
-                )
-                .expect("Could not convert subdir name from &str to String");
+                let subdir_name = current_line
+                    .split_whitespace()
+                    .nth(2)
+                    .expect("Could not get subdir name");
 
                 trace!("Subdir to change into is {}", subdir_name);
 
@@ -241,7 +253,7 @@ fn process_input(
                 let mut next_subdir_index: usize = 0;
 
                 for i in 0..this_dir.borrow().sub_dirs.len() {
-                    let search_subdir_name = this_dir.borrow().sub_dirs[i].borrow().name.clone();
+                    let search_subdir_name = this_dir.borrow().sub_dirs[i].borrow().name;
                     // trace!(
                     //     "Searching. Current index subdir name is {} at index {}",
                     //     search_subdir_name,
@@ -277,13 +289,13 @@ fn process_input(
         } // match
     } // while loop
 
-    return current_line_index;
+    current_line_index
 }
 
 fn get_dir_sizes(dir_size_vec: &mut Vec<usize>, current_dir_ref: Rc<RefCell<PuzzleDir>>) {
     // Add details of current directory
 
-    let current_dir_name = current_dir_ref.borrow().name.clone();
+    let current_dir_name = current_dir_ref.borrow().name;
     let current_dir_size = current_dir_ref.borrow().get_size();
     trace!(
         "Inserting data for dir {} of size {}",
@@ -295,7 +307,7 @@ fn get_dir_sizes(dir_size_vec: &mut Vec<usize>, current_dir_ref: Rc<RefCell<Puzz
 
     // Condition to terminate recursion
     // If this is a leaf node, aka a directory that has no subdirs, then we return
-    if (current_dir_size == 0) | (current_dir_ref.borrow().sub_dirs.len() == 0) {
+    if (current_dir_size == 0) | current_dir_ref.borrow().sub_dirs.is_empty() {
         return;
     }
 
@@ -314,3 +326,60 @@ fn get_dir_sizes(dir_size_vec: &mut Vec<usize>, current_dir_ref: Rc<RefCell<Puzz
         get_dir_sizes(dir_size_vec, sub_dir_ref);
     }
 }
+
+/// Print the answers, the time taken to compute them, and the size of every directory
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let solve_start = Instant::now();
+    let (part1_answer, part2_answer) = solve(input_file_name);
+    let solve_time = solve_start.elapsed();
+
+    println!("answer\tPart 1\t{}", part1_answer);
+    println!("answer\tPart 2\t{}", part2_answer);
+    // The directory tree is shared by both parts, so they share a single timing
+    println!("timing\tBoth parts\t{}", solve_time.as_nanos());
+
+    // Build the tree again to walk it for the breakdown
+    let input_buffer = input::read_input(input_file_name);
+    let input_file_lines: Vec<&str> = input_buffer.lines().collect();
+    let root_dir_ref = Rc::new(RefCell::new(PuzzleDir::new("/")));
+    process_input(&input_file_lines, 1, root_dir_ref.clone());
+
+    println!("bars\tDirectory sizes");
+    print_dir_size_breakdown(root_dir_ref, 0);
+    println!("end");
+}
+
+/// Print one "depth, name, size" line for this directory, followed by lines for all of its subdirs
+fn print_dir_size_breakdown(current_dir_ref: Rc<RefCell<PuzzleDir>>, depth: usize) {
+    let current_dir = current_dir_ref.borrow();
+    println!("{}\t{}\t{}", depth, current_dir.name, current_dir.get_size());
+
+    for sub_dir_ref in &current_dir.sub_dirs {
+        print_dir_size_breakdown(sub_dir_ref.clone(), depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (95437, 24933642));
+    }
+
+    #[test]
+    fn example_dir_sizes() {
+        let input_buffer = input::read_input("example.txt");
+        let input_file_lines: Vec<&str> = input_buffer.lines().collect();
+
+        let root_dir_ref = Rc::new(RefCell::new(PuzzleDir::new("/")));
+        process_input(&input_file_lines, 1, root_dir_ref.clone());
+
+        // Sizes of /, a, e and d as given in the puzzle text
+        let mut dir_size_vec: Vec<usize> = Vec::new();
+        get_dir_sizes(&mut dir_size_vec, root_dir_ref);
+        assert_eq!(dir_size_vec, vec![48381165, 94853, 584, 24933642]);
+    }
+}
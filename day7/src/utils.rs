@@ -1,41 +1,40 @@
-use std::{cell::RefCell, rc::Rc, str::FromStr};
+use std::{cell::RefCell, rc::Rc};
+
+// File and dir names are borrowed from the input buffer, so building the tree does not copy them
 
 #[derive(Debug)]
-pub struct PuzzleFile {
-    pub name: String,
+pub struct PuzzleFile<'a> {
+    pub name: &'a str,
     pub size: usize,
 }
 
-impl PuzzleFile {
-    pub fn new(input_name: &str, input_size: usize) -> Self {
+impl<'a> PuzzleFile<'a> {
+    pub fn new(input_name: &'a str, input_size: usize) -> Self {
         PuzzleFile {
-            name: String::from_str(input_name).expect("Could not assign file name"),
+            name: input_name,
             size: input_size,
         }
     }
 
     fn get_size(&self) -> usize {
-        return self.size;
+        self.size
     }
 }
 
-pub struct PuzzleDir {
-    pub name: String,
-    pub files: Vec<PuzzleFile>,
-    pub sub_dirs: Vec<Rc<RefCell<PuzzleDir>>>,
+pub struct PuzzleDir<'a> {
+    pub name: &'a str,
+    pub files: Vec<PuzzleFile<'a>>,
+    pub sub_dirs: Vec<Rc<RefCell<PuzzleDir<'a>>>>,
 }
 
-impl PuzzleDir {
+impl<'a> PuzzleDir<'a> {
     fn is_empty(&self) -> bool {
-        if self.files.is_empty() & self.sub_dirs.is_empty() {
-            return true;
-        }
-        return false;
+        self.files.is_empty() & self.sub_dirs.is_empty()
     }
 
-    pub fn new(input_name: &str) -> Self {
+    pub fn new(input_name: &'a str) -> Self {
         PuzzleDir {
-            name: String::from_str(input_name).expect("Could not read Dir name"),
+            name: input_name,
             files: Vec::new(),
             sub_dirs: Vec::new(),
         }
@@ -58,7 +57,7 @@ impl PuzzleDir {
             .map(|sub_dir| sub_dir.borrow().get_size())
             .sum::<usize>();
 
-        return total_size;
+        total_size
     }
 }
 
@@ -76,7 +75,7 @@ pub enum InputLineType {
     LsOutputDir,
 }
 
-pub fn get_line_type(input_line: &String) -> InputLineType {
+pub fn get_line_type(input_line: &str) -> InputLineType {
     // With the exception of the first line, there are only types of cd commands
     // "cd .." or "cd dir_name".
     if input_line.starts_with(CMD_MOVE_UP) {
@@ -98,5 +97,69 @@ pub fn get_line_type(input_line: &String) -> InputLineType {
     There is only one other type of line remaining
     Since all other cases have been handled. We can return this safely
     */
-    return InputLineType::LsOutputFile;
+    InputLineType::LsOutputFile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn get_line_type_for_every_kind_of_line() {
+        assert!(get_line_type("$ cd ..") == InputLineType::CommandCdIntoParentDir);
+        assert!(get_line_type("$ cd a") == InputLineType::CommandCdIntoDir);
+        assert!(get_line_type("$ ls") == InputLineType::CommandLsDir);
+        assert!(get_line_type("dir e") == InputLineType::LsOutputDir);
+        assert!(get_line_type("14848514 b.txt") == InputLineType::LsOutputFile);
+    }
+
+    proptest! {
+        /// Each generated entry is a dir: an index used to pick its parent among the dirs before it,
+        /// and the sizes of the files directly inside it. Entry 0 is the root.
+        #[test]
+        fn dir_size_is_sum_of_all_file_sizes_in_subtree(
+            dir_entries in prop::collection::vec(
+                (any::<usize>(), prop::collection::vec(1..1_000_000usize, 0..5)),
+                1..30,
+            )
+        ) {
+            let mut dir_refs: Vec<Rc<RefCell<PuzzleDir>>> = Vec::new();
+            let mut parent_indices: Vec<usize> = Vec::new();
+
+            for (dir_index, (parent_selector, file_sizes)) in dir_entries.iter().enumerate() {
+                let new_dir_ref = Rc::new(RefCell::new(PuzzleDir::new("dir")));
+                for &file_size in file_sizes {
+                    new_dir_ref.borrow_mut().files.push(PuzzleFile::new("file", file_size));
+                }
+
+                if dir_index > 0 {
+                    let parent_index = parent_selector % dir_index;
+                    dir_refs[parent_index].borrow_mut().sub_dirs.push(new_dir_ref.clone());
+                    parent_indices.push(parent_index);
+                } else {
+                    parent_indices.push(0);
+                }
+                dir_refs.push(new_dir_ref);
+            }
+
+            // Add each dir's own files to itself and to every one of its ancestors
+            let mut expected_dir_sizes = vec![0; dir_entries.len()];
+            for (dir_index, (_, file_sizes)) in dir_entries.iter().enumerate() {
+                let own_files_size: usize = file_sizes.iter().sum();
+                let mut ancestor_index = dir_index;
+                loop {
+                    expected_dir_sizes[ancestor_index] += own_files_size;
+                    if ancestor_index == 0 {
+                        break;
+                    }
+                    ancestor_index = parent_indices[ancestor_index];
+                }
+            }
+
+            for (dir_ref, expected_dir_size) in dir_refs.iter().zip(expected_dir_sizes) {
+                prop_assert_eq!(dir_ref.borrow().get_size(), expected_dir_size);
+            }
+        }
+    }
 }
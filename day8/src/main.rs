@@ -1,6 +1,6 @@
-use std::fs::read_to_string;
-
+use aoc_common::input;
 use log::{debug, trace};
+use std::time::Instant;
 
 const INPUT_FILENAME: &str = "puzzle.txt";
 
@@ -8,11 +8,27 @@ fn main() {
     env_logger::init();
     debug!("Starting Application!");
 
-    let input_file_lines: Vec<String> = read_to_string(INPUT_FILENAME)
-        .expect("Could not read input file!")
-        .split('\n')
-        .map(|line| String::from(line))
-        .collect();
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (visible_trees, max_scenic_score) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | How many trees are visible from outside the grid?\nAnswer: {}",
+        visible_trees
+    );
+    println!(
+        "Part 2 | What is the highest scenic score possible for any tree?\nAnswer: {}",
+        max_scenic_score
+    );
+}
+
+/// Count the visible trees in the input file's height map, and find the highest scenic score
+fn solve(input_file_name: &str) -> (usize, usize) {
+    let input_buffer = input::read_input(input_file_name);
 
     // Part 1 answer
     // All length-wise edge trees + all width-wise edge trees.
@@ -20,7 +36,7 @@ fn main() {
 
     let mut input_data_matrix: Vec<Vec<usize>> = Vec::new();
 
-    process_input(&mut input_data_matrix, input_file_lines);
+    process_input(&mut input_data_matrix, &input_buffer);
     trace!("Input Data Matrix: {:?}", input_data_matrix);
 
     //Immutable to mutable
@@ -33,61 +49,21 @@ fn main() {
     setting the visibility value of each tree to true in the visibility matrix
     */
 
-    // The visibility matrix is a matrix of boolean values that is of the same dimensions as the actual data
-    // A value of true represents visible, and vice versa.
-    let mut visibility_bool_matrix: Vec<Vec<bool>> = Vec::new();
-    populate_visibility_matrix(&input_data_matrix, &mut visibility_bool_matrix);
-    trace!("Tree Visibility Matrix: {:?}", visibility_bool_matrix);
-
-    /* Let's call our algorithm tallest-tree-so-far.
-        For each tree in a row/column, in all orientations aka
-        left-to-right + right-to-left and top-to-bottom + bottom-to-top
-        if its height is the tallest we've seen so far, we set its visibility to true.
-        We stop traversing if we come across a height that is the maximum height for the row
-        Obviously the first occurance of  a tree with this max height will be visible, but not anything after that
-
-        So overall, for each tree, we do four passes. If its visibility gets set to true even in one pass,
-        it means its visible from somewhere, which is enough
-
-        This is implemented in set_visibility_row_wise() and set_visibility_column_wise()
-        which we will call on each row and column respectively
-        and appropriately set the values in the visibility matrix
-
-        When this is done, we have a visibility matrix ready.
-    */
-
-    //Compute visibility row-wose
-    for row_index in 0..input_data_matrix.len() {
-        set_visibility_row_wise(&input_data_matrix, &mut visibility_bool_matrix, row_index);
-        trace!("--");
-    }
-
-    for column_index in 0..input_data_matrix[0].len() {
-        set_visibility_column_wise(
-            &input_data_matrix,
-            &mut visibility_bool_matrix,
-            column_index,
-        );
-        trace!("--");
-    }
+    let visibility_bool_matrix = get_visibility_matrix(&input_data_matrix);
     debug!("Printing Visibility matrix!");
-    for row_index in 0..visibility_bool_matrix.len() {
-        debug!("{:?}", visibility_bool_matrix[row_index]);
+    for visibility_row in &visibility_bool_matrix {
+        debug!("{:?}", visibility_row);
     }
 
     //Count visible trees
     let mut visible_trees = 0;
-    for row_index in 0..visibility_bool_matrix.len() {
-        for column_index in 0..visibility_bool_matrix[row_index].len() {
-            if visibility_bool_matrix[row_index][column_index] {
+    for visibility_row in &visibility_bool_matrix {
+        for &is_visible in visibility_row {
+            if is_visible {
                 visible_trees += 1;
             }
         }
     }
-    println!(
-        "Part 1 | How many trees are visible from outside the grid?\nAnswer: {}",
-        visible_trees
-    );
 
     // Solve for Part 2
 
@@ -118,25 +94,72 @@ fn main() {
             }
         }
     }
-    println!(
-        "Part 2 | What is the highest scenic score possible for any tree?\nAnswer: {}",
-        max_scenic_score
-    );
+
+    (visible_trees, max_scenic_score)
 }
 
-fn process_input(input_data_array: &mut Vec<Vec<usize>>, input_file_lines: Vec<String>) {
-    // For each input line, populate the input_data_vector
+/// Build the matrix of which trees are visible from outside the grid
+fn get_visibility_matrix(input_data_matrix: &[Vec<usize>]) -> Vec<Vec<bool>> {
+    // The visibility matrix is a matrix of boolean values that is of the same dimensions as the actual data
+    // A value of true represents visible, and vice versa.
+    let mut visibility_bool_matrix: Vec<Vec<bool>> = Vec::new();
+    populate_visibility_matrix(input_data_matrix, &mut visibility_bool_matrix);
+    trace!("Tree Visibility Matrix: {:?}", visibility_bool_matrix);
 
-    for input_file_line in input_file_lines {
-        let mut line_data_vector: Vec<usize> = Vec::new();
+    /* Let's call our algorithm tallest-tree-so-far.
+        For each tree in a row/column, in all orientations aka
+        left-to-right + right-to-left and top-to-bottom + bottom-to-top
+        if its height is the tallest we've seen so far, we set its visibility to true.
+        We stop traversing if we come across a height that is the maximum height for the row
+        Obviously the first occurance of  a tree with this max height will be visible, but not anything after that
 
+        So overall, for each tree, we do four passes. If its visibility gets set to true even in one pass,
+        it means its visible from somewhere, which is enough
+
+        This is implemented in set_visibility_row_wise() and set_visibility_column_wise()
+        which we will call on each row and column respectively
+        and appropriately set the values in the visibility matrix
+
+        When this is done, we have a visibility matrix ready.
+    */
+
+    //Compute visibility row-wose
+    for row_index in 0..input_data_matrix.len() {
+        set_visibility_row_wise(input_data_matrix, &mut visibility_bool_matrix, row_index);
+        trace!("--");
+    }
+
+    for column_index in 0..input_data_matrix[0].len() {
+        set_visibility_column_wise(
+            input_data_matrix,
+            &mut visibility_bool_matrix,
+            column_index,
+        );
+        trace!("--");
+    }
+
+    visibility_bool_matrix
+}
+
+fn process_input(input_data_array: &mut Vec<Vec<usize>>, input_string: &str) {
+    // For each input line, populate the input_data_vector
+    // Lines are borrowed straight out of the input buffer, and each height is read from its byte.
+
+    for input_file_line in input_string.lines() {
         // Call trim() to ensure whitespace on the ends are removed
-        for tree_height_str in input_file_line.trim().chars().map(|c| c.to_string()) {
-            // trace!("Parsing character height {}", tree_height_str);
-            let tree_height = tree_height_str
-                .parse::<usize>()
+        let input_file_line = input_file_line.trim();
+        if input_file_line.is_empty() {
+            continue;
+        }
+
+        let mut line_data_vector: Vec<usize> = Vec::with_capacity(input_file_line.len());
+
+        for tree_height_byte in input_file_line.bytes() {
+            // trace!("Parsing character height {}", tree_height_byte);
+            let tree_height = (tree_height_byte as char)
+                .to_digit(10)
                 .expect("Could not parse tree height integer");
-            line_data_vector.push(tree_height);
+            line_data_vector.push(tree_height as usize);
         }
 
         input_data_array.push(line_data_vector);
@@ -144,23 +167,17 @@ fn process_input(input_data_array: &mut Vec<Vec<usize>>, input_file_lines: Vec<S
 }
 
 fn populate_visibility_matrix(
-    input_data_matrix: &Vec<Vec<usize>>,
+    input_data_matrix: &[Vec<usize>],
     visibility_bool_matrix: &mut Vec<Vec<bool>>,
 ) {
     for input_data_row in input_data_matrix {
-        let mut visibility_matrix_row: Vec<bool> = Vec::new();
-
-        for _ in input_data_row {
-            visibility_matrix_row.push(false);
-        }
-
-        visibility_bool_matrix.push(visibility_matrix_row);
+        visibility_bool_matrix.push(vec![false; input_data_row.len()]);
     }
 }
 
 fn set_visibility_row_wise(
-    input_data_matrix: &Vec<Vec<usize>>,
-    visibility_bool_matrix: &mut Vec<Vec<bool>>,
+    input_data_matrix: &[Vec<usize>],
+    visibility_bool_matrix: &mut [Vec<bool>],
     row_index: usize,
 ) {
     //Get the data for the row which we are processing
@@ -256,8 +273,8 @@ fn set_visibility_row_wise(
 }
 
 fn set_visibility_column_wise(
-    input_data_matrix: &Vec<Vec<usize>>,
-    visibility_bool_matrix: &mut Vec<Vec<bool>>,
+    input_data_matrix: &[Vec<usize>],
+    visibility_bool_matrix: &mut [Vec<bool>],
     column_index: usize,
 ) {
     //We will have to do this slightly differently
@@ -265,8 +282,8 @@ fn set_visibility_column_wise(
     // Find the max height for this column
     let mut column_max_tree_height = 0;
 
-    for row_index in 0..input_data_matrix.len() {
-        let current_tree_height = input_data_matrix[row_index][column_index];
+    for input_data_row in input_data_matrix {
+        let current_tree_height = input_data_row[column_index];
 
         if (column_max_tree_height == 0) | (current_tree_height > column_max_tree_height) {
             column_max_tree_height = current_tree_height;
@@ -349,8 +366,8 @@ fn set_visibility_column_wise(
 }
 
 fn compute_scenic_score(
-    input_data_matrix: &Vec<Vec<usize>>,
-    visibility_bool_matrix: &Vec<Vec<bool>>,
+    input_data_matrix: &[Vec<usize>],
+    visibility_bool_matrix: &[Vec<bool>],
     row_index: usize,
     column_index: usize,
 ) -> usize {
@@ -383,12 +400,12 @@ fn compute_scenic_score(
     // Part 1-1 - Check row-wise visibility to the left of the tree
     let mut visible_trees_left = 0;
 
-    for current_column_index in (0..=(column_index - 1)).rev() {
+    for &current_tree_height in input_data_matrix[row_index][..column_index].iter().rev() {
         // If we got to here, then the tree in this column is visible
         visible_trees_left += 1;
 
         // This is the msot we can see. No need to go further
-        if scoring_tree_height <= input_data_matrix[row_index][current_column_index] {
+        if scoring_tree_height <= current_tree_height {
             break;
         }
     }
@@ -404,10 +421,10 @@ fn compute_scenic_score(
 
     // Part 1-2 - Check row-wise visibility to the right of the tree
     let mut visible_trees_right = 0;
-    for current_column_index in (column_index + 1)..input_data_matrix[0].len() {
+    for &current_tree_height in &input_data_matrix[row_index][(column_index + 1)..] {
         visible_trees_right += 1;
 
-        if scoring_tree_height <= input_data_matrix[row_index][current_column_index] {
+        if scoring_tree_height <= current_tree_height {
             break;
         }
     }
@@ -423,10 +440,10 @@ fn compute_scenic_score(
 
     // Part 2-1 - Check column-wise visibility above current tree
     let mut visible_trees_top = 0;
-    for current_row_index in (0..=(row_index - 1)).rev() {
+    for input_data_row in input_data_matrix[..row_index].iter().rev() {
         visible_trees_top += 1;
 
-        if scoring_tree_height <= input_data_matrix[current_row_index][column_index] {
+        if scoring_tree_height <= input_data_row[column_index] {
             break;
         }
     }
@@ -440,9 +457,9 @@ fn compute_scenic_score(
 
     // Part 2-2 - Check column-wise visibility below current tree
     let mut visible_trees_below = 0;
-    for current_row_index in (row_index + 1)..input_data_matrix.len() {
+    for input_data_row in &input_data_matrix[(row_index + 1)..] {
         visible_trees_below += 1;
-        if scoring_tree_height <= input_data_matrix[current_row_index][column_index] {
+        if scoring_tree_height <= input_data_row[column_index] {
             break;
         }
     }
@@ -454,5 +471,95 @@ fn compute_scenic_score(
     );
     scenic_score_for_this_tree *= visible_trees_below;
 
-    return scenic_score_for_this_tree;
+    scenic_score_for_this_tree
+}
+
+/// Print the answers, the time taken to compute them, and the visibility map
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let solve_start = Instant::now();
+    let (visible_trees, max_scenic_score) = solve(input_file_name);
+    let solve_time = solve_start.elapsed();
+
+    println!("answer\tPart 1\t{}", visible_trees);
+    println!("answer\tPart 2\t{}", max_scenic_score);
+    // Part 2 only scores the trees that part 1 found visible, so they share a single timing
+    println!("timing\tBoth parts\t{}", solve_time.as_nanos());
+
+    let mut input_data_matrix: Vec<Vec<usize>> = Vec::new();
+    process_input(&mut input_data_matrix, &input::read_input(input_file_name));
+    let visibility_bool_matrix = get_visibility_matrix(&input_data_matrix);
+
+    // '#' for a visible tree, '.' for a hidden one
+    println!("grid\tTree visibility map");
+    for visibility_row in &visibility_bool_matrix {
+        let visibility_row_str: String = visibility_row
+            .iter()
+            .map(|&is_visible| if is_visible { '#' } else { '.' })
+            .collect();
+        println!("{}", visibility_row_str);
+    }
+    println!("end");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_matrices() -> (Vec<Vec<usize>>, Vec<Vec<bool>>) {
+        let mut input_data_matrix: Vec<Vec<usize>> = Vec::new();
+        process_input(&mut input_data_matrix, &input::read_input("example.txt"));
+        let visibility_bool_matrix = get_visibility_matrix(&input_data_matrix);
+
+        (input_data_matrix, visibility_bool_matrix)
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (21, 8));
+    }
+
+    #[test]
+    fn example_visibility_matrix() {
+        let (_, visibility_bool_matrix) = example_matrices();
+
+        // Only the interior trees called out in the puzzle text are visible
+        assert_eq!(
+            visibility_bool_matrix,
+            vec![
+                vec![true, true, true, true, true],
+                vec![true, true, true, false, true],
+                vec![true, true, false, true, true],
+                vec![true, false, true, false, true],
+                vec![true, true, true, true, true],
+            ]
+        );
+    }
+
+    #[test]
+    fn process_input_reads_digit_matrix() {
+        let mut input_data_matrix: Vec<Vec<usize>> = Vec::new();
+        process_input(&mut input_data_matrix, "303\n255\n");
+        assert_eq!(input_data_matrix, vec![vec![3, 0, 3], vec![2, 5, 5]]);
+    }
+
+    #[test]
+    fn example_scenic_scores() {
+        let (input_data_matrix, visibility_bool_matrix) = example_matrices();
+
+        // The two trees called out in the puzzle text
+        assert_eq!(
+            compute_scenic_score(&input_data_matrix, &visibility_bool_matrix, 1, 2),
+            4
+        );
+        assert_eq!(
+            compute_scenic_score(&input_data_matrix, &visibility_bool_matrix, 3, 2),
+            8
+        );
+        // Edge trees always score zero
+        assert_eq!(
+            compute_scenic_score(&input_data_matrix, &visibility_bool_matrix, 0, 3),
+            0
+        );
+    }
 }
@@ -1,9 +1,6 @@
+use aoc_common::input;
 use log::{debug, trace, warn};
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{collections::HashSet, time::Instant};
 mod utils;
 use utils::MoveDirection;
 const INPUT_FILENAME: &str = "puzzle.txt";
@@ -11,87 +8,127 @@ const INPUT_FILENAME: &str = "puzzle.txt";
 fn main() {
     env_logger::init();
 
+    // Answers, timings and visualization data for the HTML report. See ../report
+    if std::env::args().any(|arg| arg == "--report") {
+        print_report(INPUT_FILENAME);
+        return;
+    }
+
+    let (part1_answer, part2_answer) = solve(INPUT_FILENAME);
+
+    println!(
+        "Part 1 | How many positions does the tail of the rope visit at least once?\nAnswer: {}",
+        part1_answer
+    );
+    println!(
+        "Part 2 | How many positions does the tail of the rope visit at least once?\nAnswer: {}",
+        part2_answer
+    );
+}
+
+/// Simulate the two-knot and the ten-knot ropes over the moves in the input file.
+/// Returns the number of positions visited by the tail of each
+fn solve(input_file_name: &str) -> (usize, usize) {
+    // Both parts walk the same input buffer. Each line is borrowed from it, not copied.
+    let input_buffer = input::read_input(input_file_name);
+
     warn!(" -- PART 1 START -- ");
+    let part1_answer = get_tail_move_set(&input_buffer, 2).len();
+    warn!(" -- PART 1 END -- ");
 
-    // Let's try to do this without creating any kind of grid/matrix
+    warn!(" -- PART 2 START -- ");
+    // Now solve for part 2
+    let part2_answer = get_tail_move_set(&input_buffer, 10).len();
 
-    // let starting position be similar to that of an origin on a graph aka 0,0
-    let mut part1_rope_data: Vec<[i32; 2]> = Vec::new();
-    part1_rope_data.push([0, 0]); // Head data
-    part1_rope_data.push([0, 0]); // Tail data
+    (part1_answer, part2_answer)
+}
 
-    // This is for Part 1
+/// Simulate a rope made of knot_count knots over all the moves in the input.
+/// Returns all co-ordinates that the last knot aka the end-tail visited
+fn get_tail_move_set(input_string: &str, knot_count: usize) -> HashSet<[i32; 2]> {
+    // Let's try to do this without creating any kind of grid/matrix
 
-    // Store all co-ordinates that the tail visited in the form "x,y" in a set
-    let mut tail_move_set: HashSet<String> = HashSet::new();
-    tail_move_set.insert("0,0".to_string());
+    // let starting position be similar to that of an origin on a graph aka 0,0
+    // The first knot is the head. Every knot after it is the tail of the knot before it
+    let end_tail_index = knot_count - 1;
+    let mut rope_data: Vec<[i32; 2]> = vec![[0, 0]; knot_count];
 
-    let input_file_lines =
-        BufReader::new(File::open(INPUT_FILENAME).expect("Could not open input file")).lines();
+    // Store all co-ordinates that the end-tail visited as [x, y] in a set
+    let mut tail_move_set: HashSet<[i32; 2]> = HashSet::new();
+    tail_move_set.insert([0, 0]); // Add origin aka start to list of moves made by end-tail
 
-    for input_line in input_file_lines {
-        if let Ok(current_line) = input_line {
-            process_move(current_line, &mut part1_rope_data, &mut tail_move_set);
-        } else {
-            panic!("Could not read input line!");
-        }
+    for current_line in input_string.lines() {
+        process_move(current_line, &mut rope_data, &mut tail_move_set);
     } //for loop
 
-    debug!("Final head position: {:?}", part1_rope_data[0]);
-    debug!("Final tail position: {:?}", part1_rope_data[1]);
+    debug!("Final head position: {:?}", rope_data[0]);
+    debug!(
+        "Final end-tail position: {:?}",
+        rope_data[end_tail_index]
+    );
     trace!(
         "Tail Move Set Count: {}. Moves {:?}",
         tail_move_set.len(),
         tail_move_set
     );
 
-    println!(
-        "Part 1 | How many positions does the tail of the rope visit at least once?\nAnswer: {}",
-        tail_move_set.len()
-    );
+    tail_move_set
+}
 
-    warn!(" -- PART 1 END -- ");
-    warn!(" -- PART 2 START -- ");
-    // Now solve for part 2
+/// Print the answers, the time taken to compute each of them, and the cells visited by the end-tail
+/// in the format read by the report crate
+fn print_report(input_file_name: &str) {
+    let input_buffer = input::read_input(input_file_name);
 
-    tail_move_set.clear();
+    let part1_start = Instant::now();
+    let part1_tail_move_set = get_tail_move_set(&input_buffer, 2);
+    let part1_time = part1_start.elapsed();
 
-    const END_TAIL_INDEX: usize = 9;
+    let part2_start = Instant::now();
+    let part2_tail_move_set = get_tail_move_set(&input_buffer, 10);
+    let part2_time = part2_start.elapsed();
 
-    let mut part2_rope_data: Vec<[i32; 2]> = Vec::new();
-    part2_rope_data.push([0, 0]); // Head data
-    for _ in 1..=END_TAIL_INDEX {
-        part2_rope_data.push([0, 0]);
-    }
-    tail_move_set.insert("0,0".to_string()); // Add origin aka start to list of moves made by end-tail
+    println!("answer\tPart 1\t{}", part1_tail_move_set.len());
+    println!("answer\tPart 2\t{}", part2_tail_move_set.len());
+    println!("timing\tPart 1\t{}", part1_time.as_nanos());
+    println!("timing\tPart 2\t{}", part2_time.as_nanos());
 
-    let input_file_lines =
-        BufReader::new(File::open(INPUT_FILENAME).expect("Could not open input file")).lines();
+    println!("grid\tCells visited by the tail of the two-knot rope");
+    for grid_row in get_tail_visit_grid(&part1_tail_move_set) {
+        println!("{}", grid_row);
+    }
+    println!("end");
 
-    for input_line in input_file_lines {
-        if let Ok(current_line) = input_line {
-            process_move(current_line, &mut part2_rope_data, &mut tail_move_set);
-        } else {
-            panic!("Could not read input line!");
-        }
-    } //for loop
+    println!("grid\tCells visited by the tail of the ten-knot rope");
+    for grid_row in get_tail_visit_grid(&part2_tail_move_set) {
+        println!("{}", grid_row);
+    }
+    println!("end");
+}
 
-    debug!("Final head position: {:?}", part2_rope_data[0]);
-    debug!(
-        "Final end-tail position: {:?}",
-        part2_rope_data[END_TAIL_INDEX]
-    );
-    println!(
-        "Part 2 | How many positions does the tail of the rope visit at least once?\nAnswer: {}",
-        tail_move_set.len()
-    );
+/// Draw the visited cells as rows of '#' (visited) and '.' (not visited), from the top row to the bottom.
+/// The grid only spans the area the tail moved in, and y increases upwards like the puzzle's moves do
+fn get_tail_visit_grid(tail_move_set: &HashSet<[i32; 2]>) -> Vec<String> {
+    let min_x = tail_move_set.iter().map(|position| position[0]).min().unwrap_or(0);
+    let max_x = tail_move_set.iter().map(|position| position[0]).max().unwrap_or(0);
+    let min_y = tail_move_set.iter().map(|position| position[1]).min().unwrap_or(0);
+    let max_y = tail_move_set.iter().map(|position| position[1]).max().unwrap_or(0);
+
+    (min_y..=max_y)
+        .rev()
+        .map(|y| {
+            (min_x..=max_x)
+                .map(|x| if tail_move_set.contains(&[x, y]) { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
 }
 
 /// The tail_move_set will contain the moves made by the last tail
 fn process_move(
-    input_line: String,
-    rope_data: &mut Vec<[i32; 2]>,
-    tail_move_set: &mut HashSet<String>,
+    input_line: &str,
+    rope_data: &mut [[i32; 2]],
+    tail_move_set: &mut HashSet<[i32; 2]>,
 ) {
     // Get move details
     let (move_type, move_magnitude) =
@@ -137,7 +174,6 @@ fn process_move(
                     current_tail_number - 1,
                     current_tail_number - 2
                 );
-            
             } else {
                 match move_type {
                     MoveDirection::MoveUp => {
@@ -154,7 +190,7 @@ fn process_move(
                     }
                 }
             }
-            
+
             trace!(
                 "Head Index {} Position: {},{} | Tail Index {} Position: {},{} | before tail move",
                 current_tail_number - 1,
@@ -188,10 +224,12 @@ fn process_move(
     } //while loop
 }
 
-fn get_move_details(input_line: String) -> Option<(MoveDirection, i32)> {
-    let line_parts: Vec<&str> = input_line.split_whitespace().collect();
+fn get_move_details(input_line: &str) -> Option<(MoveDirection, i32)> {
+    let (move_str, move_magnitude_str) = input_line
+        .split_once(' ')
+        .expect("Could not split move line into direction and magnitude!");
 
-    let move_type: MoveDirection = match line_parts[0] {
+    let move_type: MoveDirection = match move_str {
         "U" => MoveDirection::MoveUp,
         "D" => MoveDirection::MoveDown,
         "L" => MoveDirection::MoveLeft,
@@ -201,39 +239,35 @@ fn get_move_details(input_line: String) -> Option<(MoveDirection, i32)> {
         }
     };
 
-    match line_parts[1].parse::<i32>() {
-        Ok(move_magnitude) => return Some((move_type, move_magnitude)),
+    match move_magnitude_str.trim().parse::<i32>() {
+        Ok(move_magnitude) => Some((move_type, move_magnitude)),
         Err(err) => {
             eprintln!(
                 "Failed to parse move magnitude {} due to error {}",
-                line_parts[1], err
+                move_magnitude_str, err
             );
-            return None;
+            None
         }
     }
 }
 
-fn is_tail_touching_head(rope_data: &Vec<[i32; 2]>, current_tail_number: usize) -> bool {
+fn is_tail_touching_head(rope_data: &[[i32; 2]], current_tail_number: usize) -> bool {
     let diff_x_abs =
         rope_data[current_tail_number][0].abs_diff(rope_data[current_tail_number - 1][0]);
     let diff_y_abs =
         rope_data[current_tail_number][1].abs_diff(rope_data[current_tail_number - 1][1]);
 
-    if (diff_x_abs <= 1) & (diff_y_abs <= 1) {
-        return true;
-    }
-
-    return false;
+    (diff_x_abs <= 1) & (diff_y_abs <= 1)
 }
 
 /// Function to be called immediately after head is moved. Assuming co-ord distance between is only two in either x or y axis
 fn make_tail_touch_head(
-    rope_data: &mut Vec<[i32; 2]>,
+    rope_data: &mut [[i32; 2]],
     tail_number: usize,
-    tail_move_set: &mut HashSet<String>,
+    tail_move_set: &mut HashSet<[i32; 2]>,
 ) {
     //Safety check. Ideally this function is never called if the head and tail are touching
-    if is_tail_touching_head(&rope_data, tail_number) {
+    if is_tail_touching_head(rope_data, tail_number) {
         return;
     }
     // So distance of two in either x or y values between head and tail
@@ -285,10 +319,118 @@ fn make_tail_touch_head(
             "Adding tail position data to move set for tail number {}",
             tail_number
         );
-        let mut new_tail_pos_str = rope_data[tail_number][0].to_string();
-        new_tail_pos_str.push(',');
-        new_tail_pos_str.push_str(rope_data[tail_number][1].to_string().as_str());
+        tail_move_set.insert(rope_data[tail_number]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve("example.txt"), (13, 1));
+    }
 
-        tail_move_set.insert(new_tail_pos_str);
+    #[test]
+    fn larger_example_answers() {
+        assert_eq!(solve("example-p2.txt"), (88, 36));
+    }
+
+    #[test]
+    fn example_tail_visit_grid() {
+        let input_buffer = input::read_input("example.txt");
+        let tail_move_set = get_tail_move_set(&input_buffer, 2);
+
+        // The picture of visited positions from the puzzle text, trimmed to the visited area
+        assert_eq!(
+            get_tail_visit_grid(&tail_move_set),
+            vec!["..##.", "...##", ".####", "....#", "####."]
+        );
+    }
+
+    #[test]
+    fn get_move_details_reads_direction_and_magnitude() {
+        assert!(matches!(
+            get_move_details("R 4"),
+            Some((MoveDirection::MoveRight, 4))
+        ));
+        assert!(matches!(
+            get_move_details("U 17"),
+            Some((MoveDirection::MoveUp, 17))
+        ));
+        assert!(get_move_details("L x").is_none());
+    }
+
+    #[test]
+    fn is_tail_touching_head_includes_overlap_and_diagonals() {
+        assert!(is_tail_touching_head(&[[0, 0], [0, 0]], 1));
+        assert!(is_tail_touching_head(&[[1, 0], [0, 0]], 1));
+        assert!(is_tail_touching_head(&[[1, 1], [0, 0]], 1));
+        assert!(is_tail_touching_head(&[[-1, 1], [0, 0]], 1));
+        assert!(!is_tail_touching_head(&[[2, 0], [0, 0]], 1));
+        assert!(!is_tail_touching_head(&[[2, 1], [0, 0]], 1));
+    }
+
+    #[test]
+    fn make_tail_touch_head_moves_straight_and_diagonally() {
+        let mut tail_move_set: HashSet<[i32; 2]> = HashSet::new();
+
+        let mut rope_data = [[2, 0], [0, 0]];
+        make_tail_touch_head(&mut rope_data, 1, &mut tail_move_set);
+        assert_eq!(rope_data[1], [1, 0]);
+
+        let mut rope_data = [[2, 1], [0, 0]];
+        make_tail_touch_head(&mut rope_data, 1, &mut tail_move_set);
+        assert_eq!(rope_data[1], [1, 1]);
+
+        // Only the last knot's positions are recorded
+        assert_eq!(tail_move_set, HashSet::from([[1, 0], [1, 1]]));
+    }
+
+    /// A move line, as found in the puzzle input
+    fn any_move_line() -> impl Strategy<Value = String> {
+        ("[UDLR]", 1..=20).prop_map(|(move_str, move_magnitude)| {
+            format!("{} {}", move_str, move_magnitude)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn tail_touches_head_after_catching_up(
+            tail_x in -1000..1000,
+            tail_y in -1000..1000,
+            head_offset in (-2..=2, -2..=2),
+        ) {
+            // A head that just moved one step away can be at most two steps away in each direction
+            let mut rope_data = [[tail_x + head_offset.0, tail_y + head_offset.1], [tail_x, tail_y]];
+            let mut tail_move_set: HashSet<[i32; 2]> = HashSet::new();
+
+            make_tail_touch_head(&mut rope_data, 1, &mut tail_move_set);
+
+            prop_assert!(is_tail_touching_head(&rope_data, 1));
+            // The tail only ever takes a single step, which may be diagonal
+            prop_assert!(rope_data[1][0].abs_diff(tail_x) <= 1);
+            prop_assert!(rope_data[1][1].abs_diff(tail_y) <= 1);
+        }
+
+        #[test]
+        fn every_knot_touches_the_next_after_each_move(
+            move_lines in prop::collection::vec(any_move_line(), 1..50),
+            knot_count in 2..=10usize,
+        ) {
+            let mut rope_data: Vec<[i32; 2]> = vec![[0, 0]; knot_count];
+            let mut tail_move_set: HashSet<[i32; 2]> = HashSet::from([[0, 0]]);
+
+            for move_line in &move_lines {
+                process_move(move_line, &mut rope_data, &mut tail_move_set);
+
+                for tail_number in 1..knot_count {
+                    prop_assert!(is_tail_touching_head(&rope_data, tail_number));
+                }
+                prop_assert!(tail_move_set.contains(&rope_data[knot_count - 1]));
+            }
+        }
     }
 }
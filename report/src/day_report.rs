@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use log::trace;
+
+/*
+Each day prints its report data when run with `--report`. The format is line based, with tab separated fields.
+
+answer<TAB>Part 1<TAB>24000
+timing<TAB>Part 1<TAB>123456             <- nanoseconds. The label can also be "Both parts"
+grid<TAB>CRT output                      <- rows of '#' and '.' follow, until "end"
+bars<TAB>Directory sizes                 <- "depth<TAB>label<TAB>value" lines follow, until "end"
+
+Any other line is ignored. So a day that does not support `--report` simply ends up with an empty report.
+*/
+
+#[derive(Debug, PartialEq)]
+pub struct Bar {
+    pub depth: usize,
+    pub label: String,
+    pub value: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Visualization {
+    Grid { title: String, rows: Vec<String> },
+    Bars { title: String, bars: Vec<Bar> },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DayReport {
+    pub day_number: u32,
+    /// (Part label, Answer)
+    pub answers: Vec<(String, String)>,
+    /// (Part label, Time taken)
+    pub timings: Vec<(String, Duration)>,
+    pub visualizations: Vec<Visualization>,
+}
+
+impl DayReport {
+    pub fn parse(day_number: u32, report_output: &str) -> Self {
+        let mut day_report = DayReport {
+            day_number,
+            answers: Vec::new(),
+            timings: Vec::new(),
+            visualizations: Vec::new(),
+        };
+
+        let mut report_lines = report_output.lines();
+
+        while let Some(report_line) = report_lines.next() {
+            trace!("Day {} | Report line: {}", day_number, report_line);
+            let line_parts: Vec<&str> = report_line.split('\t').collect();
+
+            match line_parts.as_slice() {
+                ["answer", part_label, answer] => {
+                    day_report
+                        .answers
+                        .push((part_label.to_string(), answer.to_string()));
+                }
+                ["timing", part_label, nanos] => {
+                    if let Ok(nanos) = nanos.parse::<u64>() {
+                        day_report
+                            .timings
+                            .push((part_label.to_string(), Duration::from_nanos(nanos)));
+                    }
+                }
+                ["grid", title] => {
+                    let rows = report_lines
+                        .by_ref()
+                        .take_while(|&grid_line| grid_line != "end")
+                        .map(String::from)
+                        .collect();
+                    day_report.visualizations.push(Visualization::Grid {
+                        title: title.to_string(),
+                        rows,
+                    });
+                }
+                ["bars", title] => {
+                    let bars = report_lines
+                        .by_ref()
+                        .take_while(|&bar_line| bar_line != "end")
+                        .filter_map(parse_bar)
+                        .collect();
+                    day_report.visualizations.push(Visualization::Bars {
+                        title: title.to_string(),
+                        bars,
+                    });
+                }
+                _ => continue,
+            }
+        }
+
+        day_report
+    }
+}
+
+fn parse_bar(bar_line: &str) -> Option<Bar> {
+    let mut bar_parts = bar_line.split('\t');
+
+    let depth = bar_parts.next()?.parse::<usize>().ok()?;
+    let label = bar_parts.next()?.to_string();
+    let value = bar_parts.next()?.parse::<u64>().ok()?;
+
+    Some(Bar {
+        depth,
+        label,
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_answers_timings_and_visualizations() {
+        let report_output = "answer\tPart 1\t95437\n\
+                             answer\tPart 2\t24933642\n\
+                             timing\tBoth parts\t1500\n\
+                             bars\tDirectory sizes\n\
+                             0\t/\t48381165\n\
+                             1\ta\t94853\n\
+                             end\n\
+                             grid\tMap\n\
+                             #.\n\
+                             .#\n\
+                             end\n";
+
+        let day_report = DayReport::parse(7, report_output);
+
+        assert_eq!(
+            day_report.answers,
+            vec![
+                (String::from("Part 1"), String::from("95437")),
+                (String::from("Part 2"), String::from("24933642"))
+            ]
+        );
+        assert_eq!(
+            day_report.timings,
+            vec![(String::from("Both parts"), Duration::from_nanos(1500))]
+        );
+        assert_eq!(
+            day_report.visualizations,
+            vec![
+                Visualization::Bars {
+                    title: String::from("Directory sizes"),
+                    bars: vec![
+                        Bar {
+                            depth: 0,
+                            label: String::from("/"),
+                            value: 48381165
+                        },
+                        Bar {
+                            depth: 1,
+                            label: String::from("a"),
+                            value: 94853
+                        }
+                    ]
+                },
+                Visualization::Grid {
+                    title: String::from("Map"),
+                    rows: vec![String::from("#."), String::from(".#")]
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_ignores_regular_output() {
+        let day_report = DayReport::parse(11, "Part 1 | Answer: 10605\nnot\ta\treport\tline");
+
+        assert!(day_report.answers.is_empty());
+        assert!(day_report.timings.is_empty());
+        assert!(day_report.visualizations.is_empty());
+    }
+}
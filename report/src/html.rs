@@ -0,0 +1,292 @@
+use std::{fmt::Write, time::Duration};
+
+use crate::day_report::{Bar, DayReport, Visualization};
+
+/// Grids are scaled so that they are at most this wide on the page
+const MAX_GRID_WIDTH_PX: usize = 800;
+/// ...but a single cell is never drawn bigger than this
+const MAX_GRID_CELL_PX: usize = 12;
+
+// Everything needed to display the report is inlined. No external stylesheets, scripts or fonts.
+const REPORT_STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 900px; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; vertical-align: top; }
+th { background: #f0f0f0; }
+td.number { text-align: right; font-family: monospace; }
+figure { margin: 1em 0 2em 0; }
+figcaption { font-weight: bold; margin-bottom: 0.5em; }
+svg.grid { background: #0f0f23; }
+svg.grid rect { fill: #ffff66; }
+table.bars td { border: none; padding: 0.1em 0.5em; }
+div.bar { background: #5a8dee; height: 0.9em; }
+";
+
+/// Render the report for all days as one self-contained HTML page
+pub fn render_report(day_reports: &[DayReport]) -> String {
+    let mut report_html = String::new();
+
+    report_html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    report_html.push_str("<title>Advent of Code 2022 - Run Report</title>\n");
+    let _ = writeln!(report_html, "<style>{}</style>", REPORT_STYLE);
+    report_html.push_str("</head>\n<body>\n<h1>Advent of Code 2022 - Run Report</h1>\n");
+
+    report_html.push_str(&render_answers_table(day_reports));
+
+    for day_report in day_reports {
+        if day_report.visualizations.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(report_html, "<h2>Day {}</h2>", day_report.day_number);
+        for visualization in &day_report.visualizations {
+            report_html.push_str(&render_visualization(visualization));
+        }
+    }
+
+    report_html.push_str("</body>\n</html>\n");
+
+    report_html
+}
+
+fn render_answers_table(day_reports: &[DayReport]) -> String {
+    let mut table_html = String::new();
+
+    table_html.push_str("<h2>Answers</h2>\n<table>\n");
+    table_html.push_str("<tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th></tr>\n");
+
+    for day_report in day_reports {
+        if day_report.answers.is_empty() {
+            let _ = writeln!(
+                table_html,
+                "<tr><td>{}</td><td colspan=\"3\">No report output</td></tr>",
+                day_report.day_number
+            );
+            continue;
+        }
+
+        // Days that compute both parts in the same pass only have a single timing for all of them
+        let has_timing_per_part = day_report.answers.iter().all(|(part_label, _)| {
+            day_report
+                .timings
+                .iter()
+                .any(|(timing_label, _)| timing_label == part_label)
+        });
+
+        for (answer_index, (part_label, answer)) in day_report.answers.iter().enumerate() {
+            table_html.push_str("<tr>");
+
+            if answer_index == 0 {
+                let _ = write!(
+                    table_html,
+                    "<td rowspan=\"{}\">{}</td>",
+                    day_report.answers.len(),
+                    day_report.day_number
+                );
+            }
+
+            let _ = write!(
+                table_html,
+                "<td>{}</td><td class=\"number\">{}</td>",
+                escape_html(part_label),
+                escape_html(answer)
+            );
+
+            if has_timing_per_part {
+                let (_, part_time) = day_report
+                    .timings
+                    .iter()
+                    .find(|(timing_label, _)| timing_label == part_label)
+                    .expect("Could not find timing for part");
+                let _ = write!(
+                    table_html,
+                    "<td class=\"number\">{}</td>",
+                    format_duration(*part_time)
+                );
+            } else if answer_index == 0 {
+                let shared_timings: Vec<String> = day_report
+                    .timings
+                    .iter()
+                    .map(|(timing_label, part_time)| {
+                        format!("{}: {}", escape_html(timing_label), format_duration(*part_time))
+                    })
+                    .collect();
+                let _ = write!(
+                    table_html,
+                    "<td class=\"number\" rowspan=\"{}\">{}</td>",
+                    day_report.answers.len(),
+                    shared_timings.join("<br>")
+                );
+            }
+
+            table_html.push_str("</tr>\n");
+        }
+    }
+
+    table_html.push_str("</table>\n");
+
+    table_html
+}
+
+fn render_visualization(visualization: &Visualization) -> String {
+    match visualization {
+        Visualization::Grid { title, rows } => format!(
+            "<figure>\n<figcaption>{}</figcaption>\n{}</figure>\n",
+            escape_html(title),
+            render_grid_svg(rows)
+        ),
+        Visualization::Bars { title, bars } => format!(
+            "<figure>\n<figcaption>{}</figcaption>\n{}</figure>\n",
+            escape_html(title),
+            render_bars_table(bars)
+        ),
+    }
+}
+
+/// Draw a grid of '#' and '.' cells as an inline SVG image. Every '#' is a lit cell.
+/// Runs of lit cells in a row are drawn as one rectangle, which keeps big grids small
+fn render_grid_svg(rows: &[String]) -> String {
+    let grid_width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+    let grid_height = rows.len();
+
+    if grid_width == 0 {
+        return String::from("<p>Empty grid</p>\n");
+    }
+
+    let cell_size_px = (MAX_GRID_WIDTH_PX / grid_width).clamp(1, MAX_GRID_CELL_PX);
+
+    let mut svg_html = format!(
+        "<svg class=\"grid\" xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+        grid_width,
+        grid_height,
+        grid_width * cell_size_px,
+        grid_height * cell_size_px
+    );
+
+    for (y, row) in rows.iter().enumerate() {
+        let mut run_start: Option<usize> = None;
+
+        // Chain a trailing unlit cell so that a run reaching the end of the row gets closed
+        for (x, cell) in row.chars().chain(std::iter::once('.')).enumerate() {
+            match (cell == '#', run_start) {
+                (true, None) => run_start = Some(x),
+                (false, Some(start_x)) => {
+                    let _ = writeln!(
+                        svg_html,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\"/>",
+                        start_x,
+                        y,
+                        x - start_x
+                    );
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    svg_html.push_str("</svg>\n");
+
+    svg_html
+}
+
+/// Draw the bars as a table with one row per bar. Labels are indented by depth,
+/// and bar lengths are relative to the biggest value
+fn render_bars_table(bars: &[Bar]) -> String {
+    let max_value = bars.iter().map(|bar| bar.value).max().unwrap_or(0).max(1);
+
+    let mut table_html = String::from("<table class=\"bars\">\n");
+
+    for bar in bars {
+        let bar_width_percent = (bar.value as f64) * 100.0 / (max_value as f64);
+        let _ = writeln!(
+            table_html,
+            "<tr><td style=\"padding-left: {}em\">{}</td><td class=\"number\">{}</td><td style=\"width: 300px\"><div class=\"bar\" style=\"width: {:.2}%\"></div></td></tr>",
+            bar.depth + 1,
+            escape_html(&bar.label),
+            bar.value,
+            bar_width_percent
+        );
+    }
+
+    table_html.push_str("</table>\n");
+
+    table_html
+}
+
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1_000_000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_runs_become_single_rectangles() {
+        let rows = vec![String::from("##.#"), String::from("....")];
+        let svg_html = render_grid_svg(&rows);
+
+        assert!(svg_html.contains("viewBox=\"0 0 4 2\""));
+        assert!(svg_html.contains("<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\"/>"));
+        assert!(svg_html.contains("<rect x=\"3\" y=\"0\" width=\"1\" height=\"1\"/>"));
+        assert_eq!(svg_html.matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn shared_timing_spans_all_parts() {
+        let day_report = DayReport {
+            day_number: 2,
+            answers: vec![
+                (String::from("Part 1"), String::from("15")),
+                (String::from("Part 2"), String::from("12")),
+            ],
+            timings: vec![(String::from("Both parts"), Duration::from_micros(1500))],
+            visualizations: Vec::new(),
+        };
+
+        let table_html = render_answers_table(&[day_report]);
+
+        assert!(table_html.contains("<td class=\"number\" rowspan=\"2\">Both parts: 1.50 ms</td>"));
+    }
+
+    #[test]
+    fn report_is_self_contained() {
+        let report_html = render_report(&[]);
+
+        assert!(!report_html.contains("<link"));
+        assert!(!report_html.contains("<script"));
+        assert!(!report_html.contains("src="));
+    }
+
+    #[test]
+    fn escape_html_escapes_markup() {
+        assert_eq!(escape_html("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+
+    #[test]
+    fn format_duration_picks_a_readable_unit() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_340)), "12.3 µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+}
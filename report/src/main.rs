@@ -0,0 +1,87 @@
+use log::{debug, warn};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+mod day_report;
+mod html;
+use day_report::DayReport;
+
+const REPORT_FILENAME: &str = "report.html";
+
+fn main() {
+    env_logger::init();
+
+    // The report is written to report.html, unless another path is given as the first argument
+    let report_file_name = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from(REPORT_FILENAME));
+
+    // All the day crates live next to this one
+    let repo_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("Could not find the repository directory");
+
+    let mut day_reports: Vec<DayReport> = Vec::new();
+
+    for (day_number, day_dir) in get_day_dirs(repo_dir) {
+        println!("Running day {}", day_number);
+
+        match run_day(&day_dir) {
+            Ok(report_output) => {
+                debug!("Day {} report output:\n{}", day_number, report_output);
+                day_reports.push(DayReport::parse(day_number, &report_output));
+            }
+            Err(err) => {
+                warn!("Day {} failed to run. Leaving it out of the report", day_number);
+                eprintln!("Day {} failed to run:\n{}", day_number, err);
+            }
+        }
+    }
+
+    fs::write(&report_file_name, html::render_report(&day_reports))
+        .expect("Could not write report file!");
+
+    println!("Report written to {}", report_file_name);
+}
+
+/// Find every "dayN" directory in the repository, sorted by day number
+fn get_day_dirs(repo_dir: &Path) -> Vec<(u32, PathBuf)> {
+    let mut day_dirs: Vec<(u32, PathBuf)> = fs::read_dir(repo_dir)
+        .expect("Could not read repository directory")
+        .filter_map(|dir_entry| dir_entry.ok())
+        .filter_map(|dir_entry| {
+            let day_number = dir_entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("day")?
+                .parse::<u32>()
+                .ok()?;
+            Some((day_number, dir_entry.path()))
+        })
+        .filter(|(_, day_dir)| day_dir.join("Cargo.toml").exists())
+        .collect();
+
+    day_dirs.sort();
+
+    day_dirs
+}
+
+/// Run a day in release mode with `--report`, and return what it printed
+fn run_day(day_dir: &Path) -> Result<String, String> {
+    let day_output = Command::new("cargo")
+        .args(["run", "--release", "--quiet", "--", "--report"])
+        .current_dir(day_dir)
+        // Logs go to stderr, but there's no point producing them
+        .env_remove("RUST_LOG")
+        .output()
+        .map_err(|err| err.to_string())?;
+
+    if !day_output.status.success() {
+        return Err(String::from_utf8_lossy(&day_output.stderr).into_owned());
+    }
+
+    Ok(String::from_utf8_lossy(&day_output.stdout).into_owned())
+}